@@ -1,9 +1,14 @@
+mod archive;
 mod binaries;
+mod cache;
 mod download;
 mod env;
 mod export;
 mod git;
 mod github_actions;
+mod key;
+mod proxy;
+mod timeout;
 mod utils;
 
 const SRC_BINDINGS_RS: &str = "src/bindings.rs";
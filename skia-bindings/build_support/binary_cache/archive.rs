@@ -0,0 +1,98 @@
+//! Creating and unpacking the prebuilt binaries archive.
+//!
+//! This module does not depend on the build script's environment, so that it can be tested from
+//! `tests/binary_cache.rs`.
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// The name of the tar archive without any keys or file extensions. This is also the name
+/// of the subdirectory that is created when the archive is unpacked.
+pub const ARCHIVE_NAME: &str = "skia-binaries";
+
+/// The file name of the archive with the binaries of `key`, which is the name they are downloaded
+/// and cached with.
+pub fn archive_file_name(key: &str) -> String {
+    format!("{}-{}.tar.gz", ARCHIVE_NAME, key)
+}
+
+/// Prepares the binaries directory and sets the tag.txt and key.txt
+/// file.
+pub fn prepare_export_directory(key: &str, tag: &str, artifacts: &Path) -> io::Result<PathBuf> {
+    let binaries = artifacts.join(ARCHIVE_NAME);
+    fs::create_dir_all(&binaries)?;
+
+    // this is primarily for GitHub Actions to know the tag and the key of the binaries, but they
+    // can stay inside the archive.
+
+    {
+        let mut tag_file = fs::File::create(binaries.join("tag.txt"))?;
+        tag_file.write_all(tag.as_bytes())?;
+    }
+    {
+        let mut key_file = fs::File::create(binaries.join("key.txt"))?;
+        key_file.write_all(key.as_bytes())?;
+    }
+
+    Ok(binaries)
+}
+
+/// Creates the gzip compressed tar archive `out` from the files in `export_dir`.
+///
+/// The files are stored in a directory named [`ARCHIVE_NAME`], which is the layout [`unpack()`]
+/// expects.
+pub fn archive(export_dir: &Path, out: &Path) -> io::Result<()> {
+    let file = fs::File::create(out)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    builder.append_dir_all(ARCHIVE_NAME, export_dir)?;
+    builder.into_inner()?.finish()?.sync_all()
+}
+
+/// Unpacks the archive and moves its files into `output_directory`.
+///
+/// The archive is first extracted into a temporary directory inside `output_directory`, so that a
+/// failing or truncated download does not leave a partially unpacked archive behind. The temporary
+/// directory is removed in any case.
+pub fn unpack(archive: impl Read, output_directory: &Path) -> io::Result<()> {
+    let unpack_directory = output_directory.join(format!("{}-unpack", ARCHIVE_NAME));
+    if unpack_directory.exists() {
+        fs::remove_dir_all(&unpack_directory)?;
+    }
+
+    let result = extract(archive, &unpack_directory)
+        .and_then(|_| move_files(&unpack_directory.join(ARCHIVE_NAME), output_directory));
+    let _ = fs::remove_dir_all(&unpack_directory);
+    result
+}
+
+fn extract(archive: impl Read, directory: &Path) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+    let tar = GzDecoder::new(archive);
+    // note: this creates the skia-binaries/ directory.
+    tar::Archive::new(tar).unpack(directory)
+}
+
+/// Moves all files from `from` to `to`. If one of them fails to move, the files that were already
+/// moved are removed again.
+fn move_files(from: &Path, to: &Path) -> io::Result<()> {
+    let paths = fs::read_dir(from)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+
+    let mut moved = Vec::new();
+    for path in paths {
+        let target_path = to.join(path.file_name().unwrap());
+        if let Err(e) = fs::rename(&path, &target_path) {
+            for path in moved {
+                let _ = fs::remove_file(path);
+            }
+            return Err(e);
+        }
+        moved.push(target_path);
+    }
+    Ok(())
+}
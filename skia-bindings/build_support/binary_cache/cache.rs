@@ -0,0 +1,69 @@
+//! The on-disk cache of downloaded prebuilt binaries archives.
+//!
+//! This module does not depend on the build script's environment, so that it can be tested from
+//! `tests/binary_cache.rs`.
+
+use super::archive;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader},
+    path::Path,
+};
+
+/// Unpacks the archive of `key` into `output_directory`.
+///
+/// If `cache_directory` contains the archive, it is used without calling `download`. Otherwise
+/// `download` is called to write the archive to the path it is given, and the archive is moved
+/// into `cache_directory` after it was unpacked successfully.
+pub fn install_archive(
+    key: &str,
+    output_directory: &Path,
+    cache_directory: Option<&Path>,
+    download: impl FnOnce(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let archive_name = archive::archive_file_name(key);
+    let cached_archive = cache_directory.map(|dir| dir.join(&archive_name));
+
+    let (archive_path, downloaded) = match &cached_archive {
+        Some(path) if path.is_file() => {
+            println!("USING CACHED ARCHIVE: {}", path.to_str().unwrap());
+            (path.clone(), false)
+        }
+        _ => {
+            // The process id keeps concurrent builds from writing to the same file.
+            let download_path = cached_archive
+                .clone()
+                .unwrap_or_else(|| output_directory.join(&archive_name))
+                .with_extension(format!("{}.partial", std::process::id()));
+            fs::create_dir_all(download_path.parent().unwrap())?;
+            let result = download(&download_path);
+            if result.is_err() {
+                let _ = fs::remove_file(&download_path);
+            }
+            result?;
+            (download_path, true)
+        }
+    };
+
+    println!(
+        "UNPACKING ARCHIVE INTO: {}",
+        output_directory.to_str().unwrap()
+    );
+    let unpacked = archive::unpack(BufReader::new(File::open(&archive_path)?), output_directory);
+
+    if downloaded {
+        // Only archives that could be unpacked are cached.
+        match (&unpacked, &cached_archive) {
+            (Ok(_), Some(path)) => {
+                if let Err(e) = fs::rename(&archive_path, path) {
+                    println!("CACHING ARCHIVE FAILED: {}", e);
+                    let _ = fs::remove_file(&archive_path);
+                }
+            }
+            _ => {
+                let _ = fs::remove_file(&archive_path);
+            }
+        }
+    }
+    unpacked
+}
@@ -1,12 +1,15 @@
-use super::{binaries, env, git, utils, SRC_BINDINGS_RS};
+use super::{binaries, cache, env, git, key, utils, SRC_BINDINGS_RS};
 use crate::build_support::{binaries_config, cargo};
 use flate2::read::GzDecoder;
 use std::ffi::OsStr;
-use std::fs;
+use std::fs::{self, File};
 use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
-use std::{io, io::Cursor};
+use std::{
+    io,
+    io::{BufReader, Cursor},
+};
 
 /// Resolve the skia and depot_tools subdirectory contents, either by checking out the
 /// submodules, or when the build.rs was called outside of the git repository,
@@ -131,7 +134,26 @@ fn dependencies() -> &'static [Dependency] {
 
 impl binaries_config::BinariesConfiguration {
     pub fn key(&self, repository_short_hash: &str) -> String {
-        binaries::key(repository_short_hash, &self.feature_ids, self.skia_debug)
+        let skia = cargo::get_metadata()
+            .into_iter()
+            .find(|(name, _)| name == "skia")
+            .map(|(_, skia)| skia)
+            .expect("metadata entry 'skia' not found");
+        let skia_milestone = key::skia_milestone(&skia);
+        if skia_milestone.is_none() {
+            cargo::warning(format!(
+                "Failed to parse the Skia milestone from '{}', the binaries key will not contain it",
+                skia
+            ));
+        }
+        key::key(
+            repository_short_hash,
+            skia_milestone,
+            &cargo::target().to_string(),
+            &self.feature_ids,
+            cargo::target_crt_static(),
+            self.skia_debug,
+        )
     }
 }
 
@@ -144,13 +166,13 @@ pub fn try_prepare_download(binaries_config: &binaries_config::BinariesConfigura
                 "TRYING TO DOWNLOAD AND INSTALL SKIA BINARIES: {}/{}",
                 tag, key
             );
-            let url = binaries::download_url(
+            let url = key::download_url(
                 env::skia_binaries_url().unwrap_or_else(env::skia_binaries_url_default),
                 tag,
-                key,
+                &key,
             );
             println!("  FROM: {}", url);
-            if let Err(e) = download_and_install(url, &binaries_config.output_directory) {
+            if let Err(e) = download_and_install(url, &key, &binaries_config.output_directory) {
                 println!("DOWNLOAD AND INSTALL FAILED: {}", e);
                 if force_download {
                     panic!("Downloading of binaries was forced but failed.")
@@ -189,16 +211,55 @@ fn should_try_download_binaries(
     None
 }
 
-fn download_and_install(url: impl AsRef<str>, output_directory: &Path) -> io::Result<()> {
-    let archive = utils::download(url)?;
-    println!(
-        "UNPACKING ARCHIVE INTO: {}",
-        output_directory.to_str().unwrap()
-    );
-    binaries::unpack(Cursor::new(archive), output_directory)?;
+fn download_and_install(
+    url: impl AsRef<str>,
+    key: &str,
+    output_directory: &Path,
+) -> io::Result<()> {
+    let url = url.as_ref();
+    cache::install_archive(
+        key,
+        output_directory,
+        cache_directory(output_directory).as_deref(),
+        |path| download_and_verify(url, path),
+    )?;
+
     // TODO: verify key?
     println!("INSTALLING BINDINGS");
     fs::copy(output_directory.join("bindings.rs"), SRC_BINDINGS_RS)?;
 
     Ok(())
 }
+
+/// Streams the archive at `url` to `path` and verifies its digest, if one is available.
+fn download_and_verify(url: &str, path: &Path) -> io::Result<()> {
+    utils::download_to(url, path)?;
+    if let Some(expected) = expected_sha256(url) {
+        println!("VERIFYING ARCHIVE DIGEST");
+        binaries::verify_sha256(BufReader::new(File::open(path)?), &expected)?;
+    }
+    Ok(())
+}
+
+/// The directory downloaded archives are cached in, either set by the environment, or
+/// `skia-binaries-cache/` in the target directory of the build profile.
+fn cache_directory(output_directory: &Path) -> Option<PathBuf> {
+    env::skia_binaries_cache().or_else(|| {
+        // output_directory is `target/{profile}/build/skia-bindings-{hash}/out`
+        output_directory
+            .ancestors()
+            .nth(3)
+            .map(|profile_dir| profile_dir.join("skia-binaries-cache"))
+    })
+}
+
+/// The expected SHA-256 digest of the archive, either from the environment or from the `.sha256`
+/// file next to the archive.
+fn expected_sha256(archive_url: &str) -> Option<String> {
+    env::skia_binaries_sha256().or_else(|| {
+        let digest_url = format!("{}.sha256", archive_url);
+        // The digest file is optional, so don't retry.
+        let digest = utils::download_with_retries(&digest_url, 0).ok()?;
+        String::from_utf8(digest).ok()
+    })
+}
@@ -1,4 +1,6 @@
+use super::{proxy, timeout};
 use crate::build_support::cargo;
+use std::{path::PathBuf, time::Duration};
 
 /// Returns `true` if the download of prebuilt binaries should be forced.
 ///
@@ -24,6 +26,75 @@ pub fn skia_binaries_url_default() -> String {
         .into()
 }
 
+/// The expected SHA-256 digest of the prebuilt binaries archive as a hex string.
+///
+/// If not set, the digest is taken from a `.sha256` file next to the archive, and if there is none,
+/// the archive is not verified.
+pub fn skia_binaries_sha256() -> Option<String> {
+    cargo::env_var("SKIA_BINARIES_SHA256")
+}
+
+/// The number of times a failed download is retried before giving up, defaults to 3.
+pub fn skia_binaries_download_retries() -> usize {
+    cargo::env_var("SKIA_BINARIES_DOWNLOAD_RETRIES")
+        .map(|retries| {
+            retries
+                .parse()
+                .expect("SKIA_BINARIES_DOWNLOAD_RETRIES must be a non-negative number")
+        })
+        .unwrap_or(3)
+}
+
+/// The time the download of the prebuilt binaries may take in total, including all retries, read
+/// from `SKIA_BINARIES_TIMEOUT_SECS`. Not limited by default.
+pub fn skia_binaries_timeout() -> Option<Duration> {
+    cargo::env_var("SKIA_BINARIES_TIMEOUT_SECS").map(|secs| {
+        timeout::parse_timeout_secs(&secs)
+            .expect("SKIA_BINARIES_TIMEOUT_SECS must be a positive number of seconds")
+    })
+}
+
+/// Returns `true` if the prebuilt binaries should only be downloaded over IPv4, read from
+/// `SKIA_BINARIES_FORCE_IPV4`.
+///
+/// This helps on hosts where the download server resolves to an IPv6 address that is not
+/// reachable.
+pub fn skia_binaries_force_ipv4() -> bool {
+    cargo::env_var("SKIA_BINARIES_FORCE_IPV4").is_some()
+}
+
+/// The directory downloaded prebuilt binaries archives are cached in, read from
+/// `SKIA_BINARIES_CACHE`.
+pub fn skia_binaries_cache() -> Option<PathBuf> {
+    cargo::env_var("SKIA_BINARIES_CACHE").map(PathBuf::from)
+}
+
+/// The proxy to download the prebuilt binaries through, read from `https_proxy` or
+/// `HTTPS_PROXY`.
+pub fn https_proxy() -> Option<String> {
+    cargo::env_var("https_proxy").or_else(|| cargo::env_var("HTTPS_PROXY"))
+}
+
+/// The user name and password to authenticate at the proxy with, read from `PROXY_USER` and
+/// `PROXY_PASS`. Credentials that are part of the proxy URL take precedence.
+pub fn proxy_credentials() -> Option<(String, String)> {
+    let user = cargo::env_var("PROXY_USER")?;
+    let password = cargo::env_var("PROXY_PASS").unwrap_or_default();
+    Some((user, password))
+}
+
+/// Hosts that are accessed directly and not through the proxy, read from `no_proxy` or
+/// `NO_PROXY`.
+///
+/// The variable contains a comma separated list of host names, each matching the host itself and
+/// all its subdomains. `*` matches all hosts.
+pub fn no_proxy() -> Vec<String> {
+    cargo::env_var("no_proxy")
+        .or_else(|| cargo::env_var("NO_PROXY"))
+        .map(|hosts| proxy::parse_no_proxy(&hosts))
+        .unwrap_or_default()
+}
+
 /// Force to build Skia, even if there is a binary available.
 pub fn force_skia_build() -> bool {
     cargo::env_var("FORCE_SKIA_BUILD").is_some()
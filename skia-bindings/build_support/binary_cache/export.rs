@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use crate::build_support::binaries_config::BinariesConfiguration;
-use crate::build_support::binary_cache::{binaries, SKIA_LICENSE, SRC_BINDINGS_RS};
+use crate::build_support::binary_cache::{archive, binaries, git, SKIA_LICENSE, SRC_BINDINGS_RS};
 
 /// Publish the binaries to Azure.
 pub fn publish(binaries_config: &BinariesConfiguration, staging_directory: &Path) {
@@ -16,5 +16,16 @@ pub fn publish(binaries_config: &BinariesConfiguration, staging_directory: &Path
         (SKIA_LICENSE, "LICENSE_SKIA"),
     ];
     binaries::export(binaries_config, source_files, staging_directory)
-        .expect("EXPORTING BINARIES FAILED")
+        .expect("EXPORTING BINARIES FAILED");
+
+    // The archive is named like the ones that are downloaded, so that it can be served from a
+    // local `SKIA_BINARIES_URL`.
+    let key = binaries_config.key(&git::half_hash().unwrap());
+    let archive_path = staging_directory.join(archive::archive_file_name(&key));
+    println!("ARCHIVING BINARIES TO {}", archive_path.to_str().unwrap());
+    archive::archive(
+        &staging_directory.join(archive::ARCHIVE_NAME),
+        &archive_path,
+    )
+    .expect("ARCHIVING BINARIES FAILED")
 }
@@ -0,0 +1,116 @@
+//! The key and the download URL that identify prebuilt binaries.
+//!
+//! This module does not depend on the build script's environment, so that it can be tested from
+//! `tests/binary_cache.rs`.
+
+/// Key generation function.
+/// The resulting string will uniquely identify the generated binaries.
+/// Every part of the key is separated by '-' and no grouping / enclosing characters are used
+/// because GitHub strips them from the filenames (tested "<>[]{}()",
+/// and also Unicode characters seem to be stripped).
+///
+/// `skia_milestone` is left out if it is not known, which results in the key format of versions
+/// before the milestone was added.
+pub fn key(
+    repository_short_hash: &str,
+    skia_milestone: Option<&str>,
+    target: &str,
+    features: &[impl AsRef<str>],
+    crt_static: bool,
+    skia_debug: bool,
+) -> String {
+    let mut components = Vec::new();
+
+    fn group(str: impl AsRef<str>) -> String {
+        // no grouping syntax ATM
+        str.as_ref().to_string()
+    }
+
+    // SHA hash of the rust-skia repository.
+    components.push(repository_short_hash.to_owned());
+
+    // The Skia milestone, so that binaries of different Skia versions never share a key, even if
+    // the repository hash is the same.
+    if let Some(skia_milestone) = skia_milestone {
+        components.push(skia_milestone.to_owned());
+    }
+
+    // The target architecture, vendor, system, and abi if specified.
+    components.push(group(target));
+
+    // features, sorted and duplicates removed.
+    if let Some(features) = features_component(features) {
+        components.push(group(features));
+    };
+
+    if crt_static {
+        components.push("static".into());
+    }
+
+    if skia_debug {
+        components.push("debug".into())
+    }
+
+    components.join("-")
+}
+
+/// The Skia milestone, for example `m99`, parsed from the `skia` entry of the [package.metadata]
+/// section, for example `m99-0.47.1`, which names the branch of the Skia repository that is
+/// built.
+///
+/// Returns `None` if `skia_metadata` does not start with a milestone.
+pub fn skia_milestone(skia_metadata: &str) -> Option<&str> {
+    let milestone = skia_metadata.split('-').next()?;
+    let number = milestone.strip_prefix('m')?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(milestone)
+}
+
+/// The maximum length of the features that are joined into the key. Longer feature sets are
+/// represented by a digest.
+pub const MAX_FEATURES_COMPONENT_LEN: usize = 64;
+
+/// The separator between the features in the key. The other parts of the key are separated by
+/// '-', which is also used inside of feature names, so the features need a different one. Feature
+/// ids must not contain it.
+pub const FEATURES_SEPARATOR: char = '_';
+
+/// The key component that represents the features.
+///
+/// Features are sorted, deduplicated, and joined with [`FEATURES_SEPARATOR`]. If the result gets
+/// too long for a filename, a short digest of the feature set is used instead.
+pub fn features_component(features: &[impl AsRef<str>]) -> Option<String> {
+    use sha2::{Digest, Sha256};
+
+    if features.is_empty() {
+        return None;
+    }
+
+    let mut features: Vec<&str> = features.iter().map(|f| f.as_ref()).collect();
+    features.sort_unstable();
+    features.dedup();
+
+    if let Some(feature) = features.iter().find(|f| f.contains(FEATURES_SEPARATOR)) {
+        panic!(
+            "feature id '{}' must not contain '{}'",
+            feature, FEATURES_SEPARATOR
+        );
+    }
+
+    let joined = features.join(&FEATURES_SEPARATOR.to_string());
+    if joined.len() <= MAX_FEATURES_COMPONENT_LEN {
+        return Some(joined);
+    }
+
+    let digest = format!("{:x}", Sha256::digest(features.join("\0").as_bytes()));
+    Some(format!("features-{}", &digest[..16]))
+}
+
+/// Prepare the final download URL for the prebuilt binaries archive.
+pub fn download_url(url_template: String, tag: impl AsRef<str>, key: impl AsRef<str>) -> String {
+    url_template
+        .replace("{tag}", tag.as_ref())
+        .replace("{key}", key.as_ref())
+}
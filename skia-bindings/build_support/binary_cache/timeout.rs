@@ -0,0 +1,16 @@
+//! The time limit of downloading the prebuilt binaries.
+//!
+//! This module does not depend on the build script's environment, so that it can be tested from
+//! `tests/binary_cache.rs`.
+
+use std::time::Duration;
+
+/// Parses the value of `SKIA_BINARIES_TIMEOUT_SECS`, a positive number of seconds.
+///
+/// Returns `None` if the value is not a positive number.
+pub fn parse_timeout_secs(secs: &str) -> Option<Duration> {
+    match secs.trim().parse::<u64>() {
+        Ok(secs) if secs > 0 => Some(Duration::from_secs(secs)),
+        _ => None,
+    }
+}
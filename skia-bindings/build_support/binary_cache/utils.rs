@@ -1,16 +1,187 @@
-use std::io;
-use std::io::Read;
+use super::env;
+use super::proxy::proxy_for;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{io, thread};
+
+/// The timeout for connecting to each of the addresses a host resolves to. Kept short, so that an
+/// unreachable address fails over quickly to the next one.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(120);
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const DOWNLOAD_BUFFER_SIZE: usize = 64 * 1024;
+const PROGRESS_STEP_PERCENT: u64 = 10;
 
 /// Download a file from the given URL and return the data.
+///
+/// Failed downloads are retried [`env::skia_binaries_download_retries()`] times with an
+/// exponentially increasing delay. If all attempts fail, the last error is returned.
 pub fn download(url: impl AsRef<str>) -> io::Result<Vec<u8>> {
-    let resp = ureq::get(url.as_ref()).call();
-    match resp {
-        Ok(resp) => {
-            let mut reader = resp.into_reader();
-            let mut data = Vec::new();
-            reader.read_to_end(&mut data)?;
-            Ok(data)
+    download_with_retries(url, env::skia_binaries_download_retries())
+}
+
+/// Download a file from the given URL and return the data, retrying failed downloads `retries`
+/// times.
+pub fn download_with_retries(url: impl AsRef<str>, retries: usize) -> io::Result<Vec<u8>> {
+    with_retries(url.as_ref(), retries, |response| {
+        let mut data = Vec::new();
+        response.into_reader().read_to_end(&mut data)?;
+        Ok(data)
+    })
+}
+
+/// Download a file from the given URL and stream it to `dest`, without holding the data in
+/// memory.
+///
+/// If the server sends a content length, the progress is printed to stderr. Failed downloads are
+/// retried like in [`download()`], and `dest` is overwritten on every attempt.
+pub fn download_to(url: impl AsRef<str>, dest: &Path) -> io::Result<()> {
+    with_retries(
+        url.as_ref(),
+        env::skia_binaries_download_retries(),
+        |response| {
+            let content_length = response
+                .header("Content-Length")
+                .and_then(|length| length.parse::<u64>().ok());
+            let mut reader = response.into_reader();
+            let mut writer = BufWriter::new(File::create(dest)?);
+            let mut buffer = vec![0; DOWNLOAD_BUFFER_SIZE];
+            let mut received: u64 = 0;
+            let mut reported_percent = 0;
+            loop {
+                let read = match reader.read(&mut buffer) {
+                    Ok(0) => break,
+                    Ok(read) => read,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                };
+                writer.write_all(&buffer[..read])?;
+                received += read as u64;
+                if let Some(length) = content_length.filter(|length| *length > 0) {
+                    let percent = received * 100 / length;
+                    if percent >= reported_percent + PROGRESS_STEP_PERCENT {
+                        reported_percent = percent - percent % PROGRESS_STEP_PERCENT;
+                        eprintln!("DOWNLOADED {}% ({} of {} bytes)", percent, received, length);
+                    }
+                }
+            }
+            writer.flush()
+        },
+    )
+}
+
+/// Requests `url` and passes the response to `read`. The request and the reading of the response
+/// are retried `retries` times with an exponentially increasing delay.
+///
+/// If [`env::skia_binaries_timeout()`] is set, all attempts together must finish within that time,
+/// otherwise a [`io::ErrorKind::TimedOut`] error is returned.
+fn with_retries<R>(
+    url: &str,
+    retries: usize,
+    mut read: impl FnMut(ureq::Response) -> io::Result<R>,
+) -> io::Result<R> {
+    let mut agent = ureq::AgentBuilder::new()
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout_read(READ_TIMEOUT);
+    if let Some(proxy) = proxy_for(
+        url,
+        env::https_proxy().as_deref(),
+        &env::no_proxy(),
+        env::proxy_credentials(),
+    ) {
+        let proxy = ureq::Proxy::new(proxy)
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string()))?;
+        agent = agent.proxy(proxy);
+    }
+    if env::skia_binaries_force_ipv4() {
+        agent = agent.resolver(|netloc: &str| -> io::Result<Vec<SocketAddr>> {
+            Ok(netloc
+                .to_socket_addrs()?
+                .filter(|address| address.is_ipv4())
+                .collect())
+        });
+    }
+    let agent = agent.build();
+
+    let timeout = env::skia_binaries_timeout();
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let remaining = || -> io::Result<Option<Duration>> {
+        match (deadline, timeout) {
+            (Some(deadline), Some(timeout)) => deadline
+                .checked_duration_since(Instant::now())
+                .filter(|remaining| !remaining.is_zero())
+                .map(Some)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "Downloading {} did not finish within {} seconds \
+                             (SKIA_BINARIES_TIMEOUT_SECS)",
+                            url,
+                            timeout.as_secs()
+                        ),
+                    )
+                }),
+            _ => Ok(None),
+        }
+    };
+
+    let mut attempt = 1;
+    let mut delay = INITIAL_RETRY_DELAY;
+    loop {
+        let mut request = agent.get(url);
+        if let Some(remaining) = remaining()? {
+            request = request.timeout(remaining);
         }
-        Err(error) => Err(io::Error::new(io::ErrorKind::Other, error.to_string())),
+        let result = request
+            .call()
+            .map_err(|error| match error {
+                ureq::Error::Status(_, response) => status_error(&response),
+                error => io::Error::new(io::ErrorKind::Other, error.to_string()),
+            })
+            .and_then(|response| match response.status() {
+                200..=299 => read(response),
+                _ => Err(status_error(&response)),
+            });
+        match result {
+            Ok(result) => return Ok(result),
+            Err(error) => {
+                println!(
+                    "DOWNLOAD ATTEMPT {} OF {} FAILED: {} ({})",
+                    attempt,
+                    retries + 1,
+                    url,
+                    error
+                );
+                if attempt > retries {
+                    return Err(error);
+                }
+            }
+        }
+        if let Some(remaining) = remaining()? {
+            delay = delay.min(remaining);
+        }
+        thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
     }
 }
+
+/// Describes a response that did not succeed, for example a 404 or a redirect that was not
+/// followed, so that it does not get mistaken for the requested file.
+///
+/// Redirects are followed by `ureq`, so the URL reported is the one of the final response.
+fn status_error(response: &ureq::Response) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        format!(
+            "HTTP {} {} fetching {}",
+            response.status(),
+            response.status_text(),
+            response.get_url()
+        ),
+    )
+}
@@ -202,6 +202,8 @@ pub fn generate_bindings(build: &FinalBuildConfiguration, output_directory: &Pat
 
     let target = cargo::target();
 
+    disable_rtti(&mut cc_build, &target);
+
     let target_str = &target.to_string();
     cc_build.target(target_str);
 
@@ -292,6 +294,24 @@ pub fn generate_bindings(build: &FinalBuildConfiguration, output_directory: &Pat
         .expect("Couldn't write bindings!");
 }
 
+/// Compiles the binding sources without RTTI, like Skia itself.
+///
+/// Skia's `gn/BUILD.gn` compiles the library with its `no_rtti` config (`-fno-rtti`, `/GR-`), so
+/// the library does not contain the type information of its classes. Classes in the binding
+/// sources that derive from Skia classes with a key function (like `RustWStream` from
+/// `SkWStream`, or `CountingCanvas` from `SkCanvas`) would reference the type information of
+/// their base (for example `typeinfo for SkWStream`) if compiled with RTTI, and fail to link.
+///
+/// None of the binding sources use `dynamic_cast` or `typeid`. Bindgen only parses the sources
+/// and does not need the flag, because RTTI does not affect the layout of types.
+fn disable_rtti(cc_build: &mut Build, target: &cargo::Target) {
+    if let (_, _, "windows", Some("msvc")) = target.as_strs() {
+        cc_build.flag("/GR-");
+    } else {
+        cc_build.flag("-fno-rtti");
+    }
+}
+
 const ALLOWLISTED_FUNCTIONS: &[&str] = &[
     "SkAnnotateRectWithURL",
     "SkAnnotateNamedDestination",
@@ -647,6 +667,8 @@ const ENUM_TABLE: &[EnumEntry] = &[
     ("SkScanlineOrder", rewrite::k_xxx_name),
     // m94: SkRuntimeEffect::ChildType
     ("ChildType", rewrite::k_xxx_name_opt),
+    // CountingCanvas::DrawKind (bindings.cpp)
+    ("DrawKind", rewrite::k_xxx),
 ];
 
 pub(crate) mod rewrite {
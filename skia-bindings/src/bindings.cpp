@@ -88,6 +88,7 @@
 // utils/
 #include "include/utils/SkCamera.h"
 #include "include/utils/SkCustomTypeface.h"
+#include "include/utils/SkNWayCanvas.h"
 #include "include/utils/SkNullCanvas.h"
 #include "include/utils/SkOrderedFontMgr.h"
 #include "include/utils/SkParsePath.h"
@@ -379,6 +380,12 @@ extern "C" SkData *C_SkEncodeBitmap(const SkBitmap *src, SkEncodedImageFormat fo
     return SkEncodeBitmap(*src, format, quality).release();
 }
 
+extern "C" bool C_SkImage_encodeToStream(const SkImage* self, SkWStream* stream, SkEncodedImageFormat format, int quality) {
+    auto raster = self->makeRasterImage();
+    SkPixmap pixmap;
+    return raster && raster->peekPixels(&pixmap) && SkEncodeImage(stream, pixmap, format, quality);
+}
+
 //
 // core/SkData.h
 //
@@ -716,6 +723,11 @@ extern "C" SkIRect C_SkCanvas_getDeviceClipBounds(const SkCanvas* self) {
     return self->getDeviceClipBounds();
 }
 
+extern "C" SkIRect C_SkCanvas_clipRectAndGetDeviceClipBounds(SkCanvas* self, const SkRect* rect, SkClipOp op, bool doAntiAlias) {
+    self->clipRect(*rect, op, doAntiAlias);
+    return self->getDeviceClipBounds();
+}
+
 extern "C" bool C_SkCanvas_isClipEmpty(const SkCanvas* self) {
     return self->isClipEmpty();
 }
@@ -728,6 +740,14 @@ extern "C" void C_SkCanvas_getLocalToDevice(const SkCanvas* self, SkM44* uniniti
     new(uninitialized) SkM44(self->getLocalToDevice());
 }
 
+extern "C" void C_SkCanvas_getBaseProps(const SkCanvas* self, SkSurfaceProps* props) {
+    *props = self->getBaseProps();
+}
+
+extern "C" void C_SkCanvas_getTopProps(const SkCanvas* self, SkSurfaceProps* props) {
+    *props = self->getTopProps();
+}
+
 extern "C" void C_SkCanvas_getTotalMatrix(const SkCanvas* self, SkMatrix* matrix) {
     *matrix = self->getTotalMatrix();
 }
@@ -1026,6 +1046,25 @@ extern "C" void C_SkPicture_playback(const SkPicture* self, SkCanvas* canvas) {
     self->playback(canvas);
 }
 
+class RustAbortCallback : public SkPicture::AbortCallback {
+public:
+    RustAbortCallback(void* data, bool (*abort)(void*))
+        : _data(data), _abort(abort) {}
+
+    bool abort() override {
+        return _abort(_data);
+    }
+
+private:
+    void* _data;
+    bool (*_abort)(void*);
+};
+
+extern "C" void C_SkPicture_playbackWithAbort(const SkPicture* self, SkCanvas* canvas, void* data, bool (*abort)(void*)) {
+    RustAbortCallback callback(data, abort);
+    self->playback(canvas, &callback);
+}
+
 extern "C" SkRect C_SkPicture_cullRect(const SkPicture* self) {
     return self->cullRect();
 }
@@ -1953,6 +1992,44 @@ extern "C" SkStreamAsset* C_SkDynamicMemoryWStream_detachAsStream(SkDynamicMemor
     return self->detachAsStream().release();
 }
 
+// A SkWStream that forwards all writes to a Rust writer.
+
+class RustWStream : public SkWStream {
+public:
+    RustWStream(void* data, bool (*write)(void*, const void*, size_t), void (*flush)(void*))
+        : _data(data), _write(write), _flush(flush), _bytesWritten(0) {}
+
+    bool write(const void* buffer, size_t size) override {
+        if (!_write(_data, buffer, size)) {
+            return false;
+        }
+        _bytesWritten += size;
+        return true;
+    }
+
+    void flush() override {
+        _flush(_data);
+    }
+
+    size_t bytesWritten() const override {
+        return _bytesWritten;
+    }
+
+private:
+    void* _data;
+    bool (*_write)(void*, const void*, size_t);
+    void (*_flush)(void*);
+    size_t _bytesWritten;
+};
+
+extern "C" void C_RustWStream_construct(RustWStream* uninitialized, void* data, bool (*write)(void*, const void*, size_t), void (*flush)(void*)) {
+    new(uninitialized) RustWStream(data, write, flush);
+}
+
+extern "C" void C_RustWStream_destruct(RustWStream* self) {
+    self->~RustWStream();
+}
+
 //
 // core/SkYUVAInfo.h
 //
@@ -2743,6 +2820,183 @@ extern "C" SkCanvas* C_SkMakeNullCanvas() {
     return SkMakeNullCanvas().release();
 }
 
+extern "C" SkCanvas* C_SkNWayCanvas_new(int width, int height) {
+    return new SkNWayCanvas(width, height);
+}
+
+extern "C" void C_SkNWayCanvas_addCanvas(SkCanvas* self, SkCanvas* canvas) {
+    static_cast<SkNWayCanvas*>(self)->addCanvas(canvas);
+}
+
+extern "C" void C_SkNWayCanvas_removeCanvas(SkCanvas* self, SkCanvas* canvas) {
+    static_cast<SkNWayCanvas*>(self)->removeCanvas(canvas);
+}
+
+extern "C" void C_SkNWayCanvas_removeAll(SkCanvas* self) {
+    static_cast<SkNWayCanvas*>(self)->removeAll();
+}
+
+// A SkNWayCanvas that counts the draw calls it forwards.
+// The order of DrawKind must match the one used in skia-safe/src/utils/counting_canvas.rs.
+
+class CountingCanvas : public SkNWayCanvas {
+public:
+    enum DrawKind {
+        kPaint, kBehind, kPoints, kRect, kRegion, kOval, kArc, kRRect, kDRRect, kPath, kVertices,
+        kPatch, kImage, kImageRect, kImageLattice, kAtlas, kEdgeAAImageSet, kTextBlob, kGlyphs,
+        kAnnotation, kShadow, kDrawable, kPicture, kEdgeAAQuad,
+        kDrawKindCount
+    };
+
+    CountingCanvas(int width, int height) : SkNWayCanvas(width, height) {}
+
+    size_t count(DrawKind kind) const {
+        return kind >= 0 && kind < kDrawKindCount ? _counts[kind] : 0;
+    }
+
+    void resetCounts() {
+        for (auto& count : _counts) {
+            count = 0;
+        }
+    }
+
+protected:
+    void onDrawPaint(const SkPaint& paint) override {
+        _counts[kPaint]++;
+        SkNWayCanvas::onDrawPaint(paint);
+    }
+
+    void onDrawBehind(const SkPaint& paint) override {
+        _counts[kBehind]++;
+        SkNWayCanvas::onDrawBehind(paint);
+    }
+
+    void onDrawPoints(PointMode mode, size_t count, const SkPoint pts[], const SkPaint& paint) override {
+        _counts[kPoints]++;
+        SkNWayCanvas::onDrawPoints(mode, count, pts, paint);
+    }
+
+    void onDrawRect(const SkRect& rect, const SkPaint& paint) override {
+        _counts[kRect]++;
+        SkNWayCanvas::onDrawRect(rect, paint);
+    }
+
+    void onDrawRegion(const SkRegion& region, const SkPaint& paint) override {
+        _counts[kRegion]++;
+        SkNWayCanvas::onDrawRegion(region, paint);
+    }
+
+    void onDrawOval(const SkRect& rect, const SkPaint& paint) override {
+        _counts[kOval]++;
+        SkNWayCanvas::onDrawOval(rect, paint);
+    }
+
+    void onDrawArc(const SkRect& rect, SkScalar startAngle, SkScalar sweepAngle, bool useCenter, const SkPaint& paint) override {
+        _counts[kArc]++;
+        SkNWayCanvas::onDrawArc(rect, startAngle, sweepAngle, useCenter, paint);
+    }
+
+    void onDrawRRect(const SkRRect& rrect, const SkPaint& paint) override {
+        _counts[kRRect]++;
+        SkNWayCanvas::onDrawRRect(rrect, paint);
+    }
+
+    void onDrawDRRect(const SkRRect& outer, const SkRRect& inner, const SkPaint& paint) override {
+        _counts[kDRRect]++;
+        SkNWayCanvas::onDrawDRRect(outer, inner, paint);
+    }
+
+    void onDrawPath(const SkPath& path, const SkPaint& paint) override {
+        _counts[kPath]++;
+        SkNWayCanvas::onDrawPath(path, paint);
+    }
+
+    void onDrawVerticesObject(const SkVertices* vertices, SkBlendMode mode, const SkPaint& paint) override {
+        _counts[kVertices]++;
+        SkNWayCanvas::onDrawVerticesObject(vertices, mode, paint);
+    }
+
+    void onDrawPatch(const SkPoint cubics[12], const SkColor colors[4], const SkPoint texCoords[4], SkBlendMode mode, const SkPaint& paint) override {
+        _counts[kPatch]++;
+        SkNWayCanvas::onDrawPatch(cubics, colors, texCoords, mode, paint);
+    }
+
+    void onDrawImage2(const SkImage* image, SkScalar dx, SkScalar dy, const SkSamplingOptions& sampling, const SkPaint* paint) override {
+        _counts[kImage]++;
+        SkNWayCanvas::onDrawImage2(image, dx, dy, sampling, paint);
+    }
+
+    void onDrawImageRect2(const SkImage* image, const SkRect& src, const SkRect& dst, const SkSamplingOptions& sampling, const SkPaint* paint, SrcRectConstraint constraint) override {
+        _counts[kImageRect]++;
+        SkNWayCanvas::onDrawImageRect2(image, src, dst, sampling, paint, constraint);
+    }
+
+    void onDrawImageLattice2(const SkImage* image, const Lattice& lattice, const SkRect& dst, SkFilterMode filter, const SkPaint* paint) override {
+        _counts[kImageLattice]++;
+        SkNWayCanvas::onDrawImageLattice2(image, lattice, dst, filter, paint);
+    }
+
+    void onDrawAtlas2(const SkImage* image, const SkRSXform xform[], const SkRect src[], const SkColor colors[], int count, SkBlendMode mode, const SkSamplingOptions& sampling, const SkRect* cull, const SkPaint* paint) override {
+        _counts[kAtlas]++;
+        SkNWayCanvas::onDrawAtlas2(image, xform, src, colors, count, mode, sampling, cull, paint);
+    }
+
+    void onDrawEdgeAAImageSet2(const ImageSetEntry imageSet[], int count, const SkPoint dstClips[], const SkMatrix preViewMatrices[], const SkSamplingOptions& sampling, const SkPaint* paint, SrcRectConstraint constraint) override {
+        _counts[kEdgeAAImageSet]++;
+        SkNWayCanvas::onDrawEdgeAAImageSet2(imageSet, count, dstClips, preViewMatrices, sampling, paint, constraint);
+    }
+
+    void onDrawTextBlob(const SkTextBlob* blob, SkScalar x, SkScalar y, const SkPaint& paint) override {
+        _counts[kTextBlob]++;
+        SkNWayCanvas::onDrawTextBlob(blob, x, y, paint);
+    }
+
+    void onDrawGlyphRunList(const SkGlyphRunList& glyphRunList, const SkPaint& paint) override {
+        _counts[kGlyphs]++;
+        SkNWayCanvas::onDrawGlyphRunList(glyphRunList, paint);
+    }
+
+    void onDrawAnnotation(const SkRect& rect, const char key[], SkData* value) override {
+        _counts[kAnnotation]++;
+        SkNWayCanvas::onDrawAnnotation(rect, key, value);
+    }
+
+    void onDrawShadowRec(const SkPath& path, const SkDrawShadowRec& rec) override {
+        _counts[kShadow]++;
+        SkNWayCanvas::onDrawShadowRec(path, rec);
+    }
+
+    void onDrawDrawable(SkDrawable* drawable, const SkMatrix* matrix) override {
+        _counts[kDrawable]++;
+        SkNWayCanvas::onDrawDrawable(drawable, matrix);
+    }
+
+    void onDrawPicture(const SkPicture* picture, const SkMatrix* matrix, const SkPaint* paint) override {
+        _counts[kPicture]++;
+        SkNWayCanvas::onDrawPicture(picture, matrix, paint);
+    }
+
+    void onDrawEdgeAAQuad(const SkRect& rect, const SkPoint clip[4], QuadAAFlags aa, const SkColor4f& color, SkBlendMode mode) override {
+        _counts[kEdgeAAQuad]++;
+        SkNWayCanvas::onDrawEdgeAAQuad(rect, clip, aa, color, mode);
+    }
+
+private:
+    size_t _counts[kDrawKindCount] = {};
+};
+
+extern "C" SkCanvas* C_CountingCanvas_new(int width, int height) {
+    return new CountingCanvas(width, height);
+}
+
+extern "C" size_t C_CountingCanvas_count(const SkCanvas* self, CountingCanvas::DrawKind kind) {
+    return static_cast<const CountingCanvas*>(self)->count(kind);
+}
+
+extern "C" void C_CountingCanvas_resetCounts(SkCanvas* self) {
+    static_cast<CountingCanvas*>(self)->resetCounts();
+}
+
 extern "C" SkOrderedFontMgr* C_SkOrderedFontMgr_new() {
     return new SkOrderedFontMgr();
 }
@@ -180,7 +180,8 @@ pub use rsxform::*;
 pub mod sampling_options;
 #[allow(deprecated)]
 pub use sampling_options::{
-    CubicResampler, FilterMode, FilterOptions, MipmapMode, SamplingMode, SamplingOptions,
+    CubicResampler, FilterMode, FilterOptions, FilterQuality, MipmapMode, SamplingMode,
+    SamplingOptions,
 };
 
 mod scalar_;
@@ -1,10 +1,11 @@
 #[cfg(feature = "gpu")]
 use crate::gpu;
 use crate::{
-    prelude::*, scalar, u8cpu, Bitmap, BlendMode, ClipOp, Color, Color4f, Data, Drawable,
-    FilterMode, Font, GlyphId, IPoint, IRect, ISize, Image, ImageFilter, ImageInfo, Matrix, Paint,
-    Path, Picture, Pixmap, Point, QuickReject, RRect, RSXform, Rect, Region, SamplingOptions,
-    Shader, Surface, SurfaceProps, TextBlob, TextEncoding, Vector, Vertices, M44,
+    paint, prelude::*, scalar, u8cpu, Bitmap, BlendMode, ClipOp, Color, Color4f, ColorSpace,
+    ColorType, Data, Drawable, FilterMode, Font, GlyphId, IPoint, IRect, ISize, Image, ImageFilter,
+    ImageInfo, Matrix, PMColor, Paint, Path, Picture, PixelGeometry, Pixmap, Point, QuickReject,
+    RRect, RSXform, Rect, Region, SamplingOptions, Shader, Surface, SurfaceProps,
+    SurfacePropsFlags, TextBlob, TextEncoding, Vector, Vertices, M44,
 };
 use skia_bindings::{
     self as sb, SkAutoCanvasRestore, SkCanvas, SkCanvas_SaveLayerRec, SkImageFilter, SkPaint,
@@ -12,7 +13,7 @@ use skia_bindings::{
 };
 use std::{
     convert::TryInto,
-    ffi::CString,
+    ffi::{c_void, CString},
     fmt,
     marker::PhantomData,
     mem,
@@ -25,11 +26,12 @@ pub use lattice::Lattice;
 bitflags! {
     /// [`SaveLayerFlags`] provides options that may be used in any combination in [`SaveLayerRec`],
     /// defining how layer allocated by [`Canvas::save_layer()`] operates. It may be set to zero,
-    /// [`PRESERVE_LCD_TEXT`], [`INIT_WITH_PREVIOUS`], or both flags.
+    /// or any combination of [`PRESERVE_LCD_TEXT`], [`INIT_WITH_PREVIOUS`], and [`F16_COLOR_TYPE`].
     pub struct SaveLayerFlags: u32 {
         const PRESERVE_LCD_TEXT = sb::SkCanvas_SaveLayerFlagsSet_kPreserveLCDText_SaveLayerFlag as _;
         /// initializes with previous contents
         const INIT_WITH_PREVIOUS = sb::SkCanvas_SaveLayerFlagsSet_kInitWithPrevious_SaveLayerFlag as _;
+        /// instead of matching previous layer's color type, use F16
         const F16_COLOR_TYPE = sb::SkCanvas_SaveLayerFlagsSet_kF16ColorType as _;
     }
 }
@@ -88,6 +90,14 @@ impl<'a> Default for SaveLayerRec<'a> {
 }
 
 impl<'a> SaveLayerRec<'a> {
+    /// Returns an empty [`SaveLayerRec`], see [`SaveLayerRec::default()`].
+    ///
+    /// Use the builder functions [`Self::bounds()`], [`Self::paint()`], [`Self::backdrop()`],
+    /// and [`Self::flags()`] to configure the layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     /// Hints at layer size limit
     #[must_use]
     pub fn bounds(self, bounds: &'a Rect) -> Self {
@@ -110,6 +120,10 @@ impl<'a> SaveLayerRec<'a> {
     /// [`SaveLayerFlags::INIT_WITH_PREVIOUS`] on [`Self::flags`]: the current layer is copied into
     /// the new layer, rather than initializing the new layer with transparent-black. This is then
     /// filtered by [`Self::backdrop`] (respecting the current clip).
+    ///
+    /// Setting [`SaveLayerFlags::INIT_WITH_PREVIOUS`] in addition is redundant: the layer is then
+    /// initialized with the _filtered_ previous contents, not the unfiltered ones.
+    /// [`Canvas::save_layer()`] asserts against this combination in debug builds.
     #[must_use]
     pub fn backdrop(self, backdrop: &'a ImageFilter) -> Self {
         Self {
@@ -153,6 +167,76 @@ variant_name!(PointMode::Polygon, point_mode_naming);
 pub use sb::SkCanvas_SrcRectConstraint as SrcRectConstraint;
 variant_name!(SrcRectConstraint::Fast, src_rect_constraint_naming);
 
+bitflags! {
+    /// Controls anti-aliasing of each edge of a quad drawn with [`Canvas::draw_edge_aa_quad()`] or
+    /// of an [`ImageSetEntry`] drawn with [`Canvas::draw_edge_aa_image_set()`].
+    ///
+    /// Edges that are shared with an adjacent quad should not be anti-aliased to avoid visible
+    /// seams.
+    pub struct QuadAAFlags: u32 {
+        const LEFT = sb::SkCanvas_QuadAAFlags_kLeft_QuadAAFlag as _;
+        const TOP = sb::SkCanvas_QuadAAFlags_kTop_QuadAAFlag as _;
+        const RIGHT = sb::SkCanvas_QuadAAFlags_kRight_QuadAAFlag as _;
+        const BOTTOM = sb::SkCanvas_QuadAAFlags_kBottom_QuadAAFlag as _;
+        const NONE = sb::SkCanvas_QuadAAFlags_kNone_QuadAAFlags as _;
+        const ALL = sb::SkCanvas_QuadAAFlags_kAll_QuadAAFlags as _;
+    }
+}
+
+/// An image and its placement in a set of images that is drawn with
+/// [`Canvas::draw_edge_aa_image_set()`].
+#[derive(Clone, Debug)]
+pub struct ImageSetEntry {
+    pub image: Image,
+    /// The part of the image to draw.
+    pub src_rect: Rect,
+    /// Where to draw the image.
+    pub dst_rect: Rect,
+    /// Index into the pre-view matrices, or `None` if no pre-view matrix is used.
+    pub matrix_index: Option<usize>,
+    pub alpha: f32,
+    /// Which edges of `dst_rect` are anti-aliased.
+    pub aa_flags: QuadAAFlags,
+    /// If `true`, the entry uses the next 4 points of the destination clips.
+    pub has_clip: bool,
+}
+
+impl ImageSetEntry {
+    /// Draws all of `image` into `dst_rect` with full opacity, no clip, and no pre-view matrix.
+    pub fn new(image: impl Into<Image>, dst_rect: impl AsRef<Rect>, aa_flags: QuadAAFlags) -> Self {
+        let image = image.into();
+        Self {
+            src_rect: Rect::from_iwh(image.width(), image.height()),
+            image,
+            dst_rect: *dst_rect.as_ref(),
+            matrix_index: None,
+            alpha: 1.0,
+            aa_flags,
+            has_clip: false,
+        }
+    }
+
+    // Like `ChildPtr::native()`, the returned entry references the image without increasing its
+    // reference counter, so it must not outlive `self` and must never be destructed.
+    fn native_unshared(&self) -> sb::SkCanvas_ImageSetEntry {
+        sb::SkCanvas_ImageSetEntry {
+            fImage: sb::sk_sp {
+                fPtr: unsafe { self.image.native_mut_force() },
+                _phantom_0: PhantomData,
+            },
+            fSrcRect: *self.src_rect.native(),
+            fDstRect: *self.dst_rect.native(),
+            fMatrixIndex: self
+                .matrix_index
+                .map(|i| i.try_into().unwrap())
+                .unwrap_or(-1),
+            fAlpha: self.alpha,
+            fAAFlags: self.aa_flags.bits(),
+            fHasClip: self.has_clip,
+        }
+    }
+}
+
 /// Provides access to Canvas's pixels.
 ///
 /// Returned by [`Canvas::access_top_layer_pixels()`]
@@ -168,6 +252,76 @@ pub struct TopLayerPixels<'a> {
     pub origin: IPoint,
 }
 
+/// The kind of the current clip of a [`Canvas`], returned by [`Canvas::clip_kind()`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ClipKind {
+    /// Nothing will draw, see [`Canvas::is_clip_empty()`].
+    Empty,
+    /// The clip is a single device aligned rectangle, see [`Canvas::is_clip_rect()`].
+    Rect,
+    /// The clip is anti-aliased, or consists of multiple rectangles or paths.
+    ///
+    /// Round rectangle clips are complex, too, even if they are not anti-aliased: the device only
+    /// keeps the resulting coverage, so their shape can not be queried afterwards.
+    Complex,
+}
+
+/// The reason why [`Canvas::try_read_pixels()`] or [`Canvas::try_write_pixels()`] failed.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PixelsError {
+    /// The pixel buffer is smaller than the [`ImageInfo`] and the row bytes require.
+    BufferTooSmall { needed: usize, got: usize },
+    /// The row bytes are too small to contain one row of pixels.
+    RowBytesTooSmall { needed: usize, got: usize },
+    /// The pixels can not be converted between the [`ImageInfo`] and the [`Canvas`], for
+    /// example because the color type is unknown.
+    ConversionUnsupported,
+    /// Skia did not copy the pixels, for example because the rectangles do not intersect, or
+    /// the pixels of the [`Canvas`] are not accessible.
+    NativeFailure,
+}
+
+impl fmt::Display for PixelsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PixelsError::BufferTooSmall { needed, got } => write!(
+                f,
+                "pixel buffer too small, needed {} bytes, got {}",
+                needed, got
+            ),
+            PixelsError::RowBytesTooSmall { needed, got } => write!(
+                f,
+                "row bytes too small, needed at least {}, got {}",
+                needed, got
+            ),
+            PixelsError::ConversionUnsupported => write!(f, "pixel conversion unsupported"),
+            PixelsError::NativeFailure => write!(f, "pixels were not copied"),
+        }
+    }
+}
+
+impl std::error::Error for PixelsError {}
+
+impl PixelsError {
+    fn check_buffer(info: &ImageInfo, len: usize, row_bytes: usize) -> Result<(), PixelsError> {
+        if info.color_type() == ColorType::Unknown {
+            return Err(PixelsError::ConversionUnsupported);
+        }
+        let min_row_bytes = info.min_row_bytes();
+        if row_bytes < min_row_bytes {
+            return Err(PixelsError::RowBytesTooSmall {
+                needed: min_row_bytes,
+                got: row_bytes,
+            });
+        }
+        let needed = info.compute_byte_size(row_bytes);
+        if len < needed {
+            return Err(PixelsError::BufferTooSmall { needed, got: len });
+        }
+        Ok(())
+    }
+}
+
 /// Used to pass either a slice of [`Point`] or [`RSXform`] to [`Canvas::draw_glyphs_at`].
 #[derive(Clone, Debug)]
 pub enum GlyphPositions<'a> {
@@ -269,12 +423,14 @@ impl Default for OwnedCanvas<'_> {
     /// Creates an empty [`Canvas`] with no backing device or pixels, with
     /// a width and height of zero.
     ///
+    /// Panics if the [`Canvas`] can not be allocated, use [`Canvas::new_empty()`] to handle that
+    /// case.
+    ///
     /// Returns empty [`Canvas`]
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_empty_constructor>
     fn default() -> Self {
-        let ptr = unsafe { sb::C_SkCanvas_newEmpty() };
-        Canvas::own_from_native_ptr(ptr).unwrap()
+        Canvas::new_empty().unwrap()
     }
 }
 
@@ -313,6 +469,11 @@ impl Canvas {
     /// Pixels are not initialized.
     /// To access pixels after drawing, call [`Self::flush()`] or [`Self::peek_pixels()`].
     ///
+    /// Skia does not support binding an existing [`Canvas`] to other pixels. To draw into the
+    /// same buffer repeatedly, for example once per frame, keep the returned [`OwnedCanvas`] and
+    /// wrap each frame in [`Self::save()`] and [`Self::restore_to_count()`], so that every frame
+    /// starts with the initial [`Matrix`] and clip.
+    ///
     /// - `info` width, height, [`crate::ColorType`], [`crate::AlphaType`], [`crate::ColorSpace`],
     ///   of raster surface; width, or height, or both, may be zero
     /// - `pixels` pointer to destination pixels buffer
@@ -342,6 +503,36 @@ impl Canvas {
         }
     }
 
+    /// Creates [`Canvas`] that draws into the pixels the [`Pixmap`] points to. The [`Pixmap`]'s
+    /// [`ImageInfo`] and row bytes describe the pixels.
+    ///
+    /// The [`Pixmap`] is borrowed mutably for the lifetime of the returned [`OwnedCanvas`]. Prefer
+    /// [`Self::from_raster_direct()`] if the pixels are available as a mutable byte slice.
+    ///
+    /// Returns [`OwnedCanvas`] if the [`Pixmap`] has pixels and its parameters are valid;
+    /// otherwise, `None`.
+    ///
+    /// # Safety
+    /// A [`Pixmap`] only borrows its pixels immutably, see [`Pixmap::new()`], but the canvas
+    /// writes to them. The caller must ensure that the pixels the `pixmap` addresses are writable
+    /// and not accessed otherwise while the returned canvas is alive, for example by using the
+    /// [`Pixmap`] returned from [`crate::Surface::peek_pixels()`].
+    pub unsafe fn from_pixmap<'pixels>(
+        pixmap: &'pixels mut Pixmap,
+    ) -> Option<OwnedCanvas<'pixels>> {
+        let addr = pixmap.writable_addr();
+        if addr.is_null() {
+            return None;
+        }
+        let ptr = sb::C_SkCanvas_MakeRasterDirect(
+            pixmap.info().native(),
+            addr,
+            pixmap.row_bytes(),
+            ptr::null(),
+        );
+        Self::own_from_native_ptr(ptr)
+    }
+
     /// Allocates raster [`Canvas`] specified by inline image specification. Subsequent [`Canvas`]
     /// calls draw into pixels.
     /// [`crate::ColorType`] is set to [`crate::ColorType::n32()`].
@@ -379,6 +570,10 @@ impl Canvas {
     /// Creates [`Canvas`] of the specified dimensions without a [`Surface`].
     /// Used by subclasses with custom implementations for draw member functions.
     ///
+    /// The [`Canvas`] does not allocate pixels, so what is drawn can not be read back, and
+    /// [`Canvas::image_snapshot()`] returns `None`. To get an [`Image`] of the drawing, draw to the
+    /// canvas of a raster [`Surface`] or to [`Canvas::from_raster_direct()`] instead.
+    ///
     /// If props equals `None`, [`SurfaceProps`] are created with `SurfaceProps::InitType` settings,
     /// which choose the pixel striping direction and order. Since a platform may dynamically change
     /// its direction when the device is rotated, and since a platform may have multiple monitors
@@ -386,7 +581,7 @@ impl Canvas {
     ///
     /// - `size` with and height zero or greater
     /// - `props` LCD striping orientation and setting for device independent fonts;
-    ///   may be `None`
+    ///   may be `None`, see [`SurfaceProps::new()`]
     /// Returns [`Canvas`] placeholder with dimensions
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_int_int_const_SkSurfaceProps_star>
@@ -410,6 +605,33 @@ impl Canvas {
         }
     }
 
+    /// Creates an empty [`Canvas`] with no backing device or pixels, with a width and height of
+    /// zero.
+    ///
+    /// Returns `None` if the [`Canvas`] could not be allocated.
+    ///
+    /// example: <https://fiddle.skia.org/c/@Canvas_empty_constructor>
+    pub fn new_empty<'lt>() -> Option<OwnedCanvas<'lt>> {
+        Canvas::own_from_native_ptr(unsafe { sb::C_SkCanvas_newEmpty() })
+    }
+
+    /// Creates [`Canvas`] of the specified dimensions without a [`Surface`], using
+    /// [`SurfaceProps`] with the given `pixel_geometry` and no flags.
+    ///
+    /// Useful for measuring or recording LCD text that should match the subpixel order of a
+    /// specific display.
+    ///
+    /// - `size` with and height zero or greater
+    /// - `pixel_geometry` LCD striping orientation
+    /// Returns [`Canvas`] placeholder with dimensions
+    pub fn new_with_pixel_geometry<'lt>(
+        size: impl Into<ISize>,
+        pixel_geometry: PixelGeometry,
+    ) -> Option<OwnedCanvas<'lt>> {
+        let props = SurfaceProps::new(SurfacePropsFlags::empty(), pixel_geometry);
+        Self::new(size, Some(&props))
+    }
+
     /// Constructs a canvas that draws into bitmap.
     /// Use props to match the device characteristics, like LCD striping.
     ///
@@ -420,15 +642,28 @@ impl Canvas {
     /// - `props` order and orientation of RGB striping; and whether to use device independent fonts
     /// Returns [`Canvas`] that can be used to draw into bitmap
     ///
+    /// Panics if the [`Canvas`] can not be allocated, use [`Canvas::try_from_bitmap()`] to handle
+    /// that case.
+    ///
     /// example: <https://fiddle.skia.org/c/@Canvas_const_SkBitmap_const_SkSurfaceProps>
     pub fn from_bitmap<'lt>(bitmap: &Bitmap, props: Option<&SurfaceProps>) -> OwnedCanvas<'lt> {
+        Self::try_from_bitmap(bitmap, props).unwrap()
+    }
+
+    /// Constructs a canvas that draws into bitmap, see [`Canvas::from_bitmap()`].
+    ///
+    /// Returns `None` if the [`Canvas`] could not be allocated.
+    pub fn try_from_bitmap<'lt>(
+        bitmap: &Bitmap,
+        props: Option<&SurfaceProps>,
+    ) -> Option<OwnedCanvas<'lt>> {
         let props_ptr = props.native_ptr_or_null();
         let ptr = if props_ptr.is_null() {
             unsafe { sb::C_SkCanvas_newFromBitmap(bitmap.native()) }
         } else {
             unsafe { sb::C_SkCanvas_newFromBitmapAndProps(bitmap.native(), props_ptr) }
         };
-        Canvas::own_from_native_ptr(ptr).unwrap()
+        Canvas::own_from_native_ptr(ptr)
     }
 
     /// Returns [`ImageInfo`] for [`Canvas`]. If [`Canvas`] is not associated with raster surface or
@@ -443,23 +678,65 @@ impl Canvas {
         ii
     }
 
+    /// Returns the width of the base layer, see [`Self::base_layer_size()`].
+    pub fn width(&self) -> i32 {
+        self.base_layer_size().width
+    }
+
+    /// Returns the height of the base layer, see [`Self::base_layer_size()`].
+    pub fn height(&self) -> i32 {
+        self.base_layer_size().height
+    }
+
+    /// Returns the [`ColorType`] of [`Canvas`]. If [`Canvas`] is not associated with raster
+    /// surface or GPU surface, [`ColorType::Unknown`] is returned.
+    pub fn color_type(&self) -> ColorType {
+        self.image_info().color_type()
+    }
+
     /// Copies [`SurfaceProps`], if [`Canvas`] is associated with raster surface or GPU surface, and
     /// returns `true`. Otherwise, returns `false` and leave props unchanged.
     ///
     /// - `props` storage for writable [`SurfaceProps`]
     /// Returns `true` if [`SurfaceProps`] was copied
     ///
+    /// These are the props of the base of the layer stack, see [`Self::base_props()`]. Layers
+    /// may use different props, see [`Self::top_props()`].
+    ///
     /// example: <https://fiddle.skia.org/c/@Canvas_getProps>
     pub fn props(&self) -> Option<SurfaceProps> {
         let mut sp = SurfaceProps::default();
         unsafe { self.native().getProps(sp.native_mut()) }.if_true_some(sp)
     }
 
+    /// Returns the [`SurfaceProps`] the canvas was created with, at the base of the layer stack.
+    pub fn base_props(&self) -> SurfaceProps {
+        let mut sp = SurfaceProps::default();
+        unsafe { sb::C_SkCanvas_getBaseProps(self.native(), sp.native_mut()) }
+        sp
+    }
+
+    /// Returns the [`SurfaceProps`] of the current top layer, which are used for drawing.
+    ///
+    /// Layers created by [`Self::save_layer()`] have an unknown pixel geometry, and so disable
+    /// LCD text, unless they are created with [`SaveLayerFlags::PRESERVE_LCD_TEXT`].
+    pub fn top_props(&self) -> SurfaceProps {
+        let mut sp = SurfaceProps::default();
+        unsafe { sb::C_SkCanvas_getTopProps(self.native(), sp.native_mut()) }
+        sp
+    }
+
+    // `getMetaData()` can not be supported: it was removed from `SkCanvas` and `SkMetaData` is
+    // private to Skia.
+
     /// Triggers the immediate execution of all pending draw operations.
     /// If [`Canvas`] is associated with GPU surface, resolves all pending GPU operations.
     /// If [`Canvas`] is associated with raster surface, has no effect; raster draw operations are
     /// never deferred.
     ///
+    /// On GPU surfaces, this does not submit the work to the GPU, use [`Self::flush_and_submit()`]
+    /// for that.
+    ///
     /// DEPRECATED: Replace usage with GrDirectContext::flush()
     #[deprecated(since = "0.38.0", note = "Replace usage with DirectContext::flush()")]
     pub fn flush(&mut self) -> &mut Self {
@@ -494,6 +771,9 @@ impl Canvas {
     /// - `props` [`SurfaceProps`] to match; may be `None` to match [`Canvas`]
     /// Returns [`Surface`] matching info and props, or `None` if no match is available
     ///
+    /// The returned [`Surface`] is independent of [`Canvas`] and has its own [`Canvas`], which is
+    /// best accessed through [`Surface::with_canvas()`].
+    ///
     /// example: <https://fiddle.skia.org/c/@Canvas_makeSurface>
     pub fn new_surface(
         &mut self,
@@ -517,12 +797,30 @@ impl Canvas {
         })
     }
 
+    /// Flushes all pending draw operations of the GPU context [`Canvas`] draws with, and submits
+    /// them to the GPU. If `sync_cpu` is `true`, blocks until the GPU has finished the work.
+    ///
+    /// If [`Canvas`] is associated with raster surface, or is not associated with a GPU context
+    /// that can submit work, this has no effect; raster draw operations are never deferred.
+    #[cfg(feature = "gpu")]
+    pub fn flush_and_submit(&mut self, sync_cpu: bool) -> &mut Self {
+        if let Some(mut context) = self
+            .recording_context()
+            .and_then(|mut context| context.as_direct_context())
+        {
+            context.flush(None);
+            context.submit(sync_cpu);
+        }
+        self
+    }
+
     /// Sometimes a canvas is owned by a surface. If it is, [`Self::surface()`] will return a bare
     /// pointer to that surface, else this will return `None`.
     ///
     /// # Safety
     /// This function is unsafe because it is not clear how exactly the lifetime of the canvas
-    /// relates to surface returned.
+    /// relates to surface returned. For example, calling [`RCHandle<SkSurface>::canvas()`] on the
+    /// returned surface creates a second mutable reference to this canvas.
     /// See also [`OwnedCanvas`], [`RCHandle<SkSurface>::canvas()`].
     pub unsafe fn surface(&mut self) -> Option<Surface> {
         // TODO: It might be possible to make this safe by returning a _kind of_ reference to the
@@ -567,7 +865,20 @@ impl Canvas {
         }
     }
 
-    // TODO: accessTopRasterHandle()
+    /// Returns custom context that tracks the [`Matrix`] and clip.
+    ///
+    /// Use `SkRasterHandleAllocator` to blend Skia drawing with custom drawing, typically performed
+    /// by the host platform user interface. The custom context returned is generated by
+    /// `SkRasterHandleAllocator::MakeCanvas`, which creates a custom canvas with raster storage for
+    /// the drawing destination.
+    ///
+    /// Returns `None` for canvases that were not created with a raster handle allocator, which
+    /// includes all raster canvases created by this crate.
+    ///
+    /// Returns context of custom allocation
+    pub fn access_top_raster_handle(&self) -> Option<ptr::NonNull<c_void>> {
+        ptr::NonNull::new(unsafe { self.native().accessTopRasterHandle() })
+    }
 
     /// Returns `true` if [`Canvas`] has direct access to its pixels.
     ///
@@ -588,6 +899,21 @@ impl Canvas {
             .if_true_then_some(move || pixmap.borrows(self))
     }
 
+    /// Returns a raster [`Image`] with a copy of the pixels of the base layer drawn so far.
+    ///
+    /// Returns `None` if the pixels are not readable, for example if the [`Canvas`] was created
+    /// with [`Canvas::new()`], which does not allocate any pixels, or if it records a picture.
+    /// For canvases of a [`Surface`], [`Surface::image_snapshot()`] avoids the copy.
+    pub fn image_snapshot(&mut self) -> Option<Image> {
+        let info = self.image_info();
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        if !self.read_pixels(&info, &mut pixels, row_bytes, (0, 0)) {
+            return None;
+        }
+        Image::from_raster_data(&info, Data::new_copy(&pixels), row_bytes)
+    }
+
     /// Copies [`Rect`] of pixels from [`Canvas`] into `dst_pixels`. [`Matrix`] and clip are
     /// ignored.
     ///
@@ -631,18 +957,49 @@ impl Canvas {
         dst_row_bytes: usize,
         src_point: impl Into<IPoint>,
     ) -> bool {
+        self.try_read_pixels(dst_info, dst_pixels, dst_row_bytes, src_point)
+            .is_ok()
+    }
+
+    /// Like [`Self::read_pixels()`], but returns a [`PixelsError`] that describes why the pixels
+    /// were not copied.
+    pub fn try_read_pixels(
+        &mut self,
+        dst_info: &ImageInfo,
+        dst_pixels: &mut [u8],
+        dst_row_bytes: usize,
+        src_point: impl Into<IPoint>,
+    ) -> Result<(), PixelsError> {
         let src_point = src_point.into();
-        let required_size = dst_info.compute_byte_size(dst_row_bytes);
-        (dst_pixels.len() >= required_size)
-            && unsafe {
-                self.native_mut().readPixels(
-                    dst_info.native(),
-                    dst_pixels.as_mut_ptr() as _,
-                    dst_row_bytes,
-                    src_point.x,
-                    src_point.y,
-                )
-            }
+        PixelsError::check_buffer(dst_info, dst_pixels.len(), dst_row_bytes)?;
+        unsafe {
+            self.native_mut().readPixels(
+                dst_info.native(),
+                dst_pixels.as_mut_ptr() as _,
+                dst_row_bytes,
+                src_point.x,
+                src_point.y,
+            )
+        }
+        .if_true_some(())
+        .ok_or(PixelsError::NativeFailure)
+    }
+
+    /// Copies [`Rect`] of pixels from [`Canvas`] into a newly allocated buffer, see
+    /// [`Self::read_pixels()`].
+    ///
+    /// The buffer uses `dst_info.min_row_bytes()` per row.
+    ///
+    /// Returns the pixels and the row bytes, or `None` if the pixels could not be read.
+    pub fn read_pixels_alloc(
+        &mut self,
+        dst_info: &ImageInfo,
+        src_point: impl Into<IPoint>,
+    ) -> Option<(Vec<u8>, usize)> {
+        let row_bytes = dst_info.min_row_bytes();
+        let mut pixels = vec![0u8; dst_info.compute_byte_size(row_bytes)];
+        self.read_pixels(dst_info, &mut pixels, row_bytes, src_point)
+            .if_true_some((pixels, row_bytes))
     }
 
     /// Copies [`Rect`] of pixels from [`Canvas`] into pixmap. [`Matrix`] and clip are
@@ -769,18 +1126,71 @@ impl Canvas {
         row_bytes: usize,
         offset: impl Into<IPoint>,
     ) -> bool {
+        self.try_write_pixels(info, pixels, row_bytes, offset)
+            .is_ok()
+    }
+
+    /// Like [`Self::write_pixels()`], but returns a [`PixelsError`] that describes why the pixels
+    /// were not written.
+    pub fn try_write_pixels(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &[u8],
+        row_bytes: usize,
+        offset: impl Into<IPoint>,
+    ) -> Result<(), PixelsError> {
         let offset = offset.into();
-        let required_size = info.compute_byte_size(row_bytes);
-        (pixels.len() >= required_size)
-            && unsafe {
-                self.native_mut().writePixels(
-                    info.native(),
-                    pixels.as_ptr() as _,
-                    row_bytes,
+        PixelsError::check_buffer(info, pixels.len(), row_bytes)?;
+        unsafe {
+            self.native_mut().writePixels(
+                info.native(),
+                pixels.as_ptr() as _,
+                row_bytes,
+                offset.x,
+                offset.y,
+            )
+        }
+        .if_true_some(())
+        .ok_or(PixelsError::NativeFailure)
+    }
+
+    /// Copies [`Rect`] from pixmap to [`Canvas`]. [`Matrix`] and clip are ignored.
+    /// Source [`Rect`] corners are `(0, 0)` and `(pixmap.width(), pixmap.height())`.
+    ///
+    /// Destination [`Rect`] corners are `(offset.x, offset.y)` and
+    /// `(image_info().width(), image_info().height())`.
+    ///
+    /// Copies each readable pixel intersecting both rectangles, without scaling,
+    /// converting to `image_info().color_type()` and `image_info().alpha_type()` if required.
+    ///
+    /// Pixel values are converted only if [`crate::ColorType`] and [`crate::AlphaType`]
+    /// do not match. Only pixels within both source and destination rectangles
+    /// are copied. [`Canvas`] pixels outside [`Rect`] intersection are unchanged.
+    ///
+    /// Does not copy, and returns `false` if:
+    /// - Source and destination rectangles do not intersect.
+    /// - pixmap does not have pixels.
+    /// - pixmap pixels could not be converted to [`Canvas`] `image_info().color_type()` or
+    ///   `image_info().alpha_type()`.
+    /// - [`Canvas`] pixels are not writable; for instance, [`Canvas`] is document based.
+    ///
+    /// - `pixmap` contains pixels copied to [`Canvas`]
+    /// - `offset` offset into [`Canvas`] writable pixels; may be negative
+    /// Returns `true` if pixels were written to [`Canvas`]
+    #[must_use]
+    pub fn write_pixels_from_pixmap(&mut self, pixmap: &Pixmap, offset: impl Into<IPoint>) -> bool {
+        let offset = offset.into();
+        unsafe {
+            let addr = pixmap.addr();
+            !addr.is_null()
+                && self.native_mut().writePixels(
+                    pixmap.info().native(),
+                    addr,
+                    pixmap.row_bytes(),
                     offset.x,
                     offset.y,
                 )
-            }
+        }
     }
 
     /// Copies [`Rect`] from pixels to [`Canvas`]. [`Matrix`] and clip are ignored.
@@ -849,6 +1259,15 @@ impl Canvas {
         unsafe { self.native_mut().save().try_into().unwrap() }
     }
 
+    /// Saves [`Matrix`] and clip like [`Self::save()`], and returns a guard that restores them
+    /// when it goes out of scope, so that no matching [`Self::restore()`] is needed.
+    ///
+    /// The guard dereferences to the [`Canvas`]. Equivalent to
+    /// `AutoCanvasRestore::guard(canvas, true)`.
+    pub fn save_guard(&mut self) -> AutoRestoredCanvas {
+        AutoCanvasRestore::guard(self, true)
+    }
+
     // The save_layer(bounds, paint) variants have been replaced by SaveLayerRec.
 
     /// Saves [`Matrix`] and clip, and allocates [`Bitmap`] for subsequent drawing.
@@ -901,11 +1320,28 @@ impl Canvas {
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_saveLayer_3>
     pub fn save_layer(&mut self, layer_rec: &SaveLayerRec) -> usize {
+        debug_assert!(
+            layer_rec.backdrop.is_none()
+                || !layer_rec.flags.contains(SaveLayerFlags::INIT_WITH_PREVIOUS),
+            "SaveLayerRec: a backdrop already initializes the layer with the filtered previous \
+             contents, SaveLayerFlags::INIT_WITH_PREVIOUS has no effect"
+        );
         unsafe { self.native_mut().saveLayer1(layer_rec.native()) }
             .try_into()
             .unwrap()
     }
 
+    /// Saves [`Matrix`] and clip and allocates a layer like [`Self::save_layer()`], and returns a
+    /// guard that restores them when it goes out of scope. The restore draws the layer onto the
+    /// previous one, so drawing into a layer that is never restored can not get lost.
+    ///
+    /// The guard dereferences to the [`Canvas`], see also [`Self::save_guard()`].
+    pub fn save_layer_guard(&mut self, layer_rec: &SaveLayerRec) -> AutoRestoredCanvas {
+        let mut guard = AutoCanvasRestore::guard(self, false);
+        guard.save_layer(layer_rec);
+        guard
+    }
+
     /// Removes changes to [`Matrix`] and clip since [`Canvas`] state was
     /// last saved. The state is removed from the stack.
     ///
@@ -931,6 +1367,19 @@ impl Canvas {
         unsafe { self.native().getSaveCount() }.try_into().unwrap()
     }
 
+    /// Panics if [`Self::save_count()`] is not `expected_save_count`.
+    ///
+    /// Use this in tests to catch a [`Self::save()`] or [`Self::save_layer()`] that was not
+    /// restored. Pass `1` to check that all saved states of a new canvas were restored.
+    pub fn assert_layers_balanced(&self, expected_save_count: usize) {
+        let save_count = self.save_count();
+        assert_eq!(
+            save_count, expected_save_count,
+            "unbalanced save / restore: save count is {}, expected {}",
+            save_count, expected_save_count
+        );
+    }
+
     /// Restores state to [`Matrix`] and clip values when [`Self::save()`], [`Self::save_layer()`],
     /// or [`Self::save_layer_alpha()`] returned `save_count`.
     ///
@@ -1033,13 +1482,21 @@ impl Canvas {
         self
     }
 
+    /// Replaces the current transform with the current transform premultiplied by `m`.
+    ///
+    /// Unlike [`Self::concat()`], this preserves the 3rd row and column, so perspective and 3D
+    /// transforms are kept intact.
+    ///
+    /// - `m` [`M44`] to apply to transform
     pub fn concat_44(&mut self, m: &M44) -> &mut Self {
         unsafe { self.native_mut().concat1(m.native()) }
         self
     }
 
     /// Replaces [`Matrix`] with `matrix`.
-    /// Unlike [`Self::concat()`], any prior matrix state is overwritten.
+    /// Unlike [`Self::concat()`], any prior matrix state is overwritten, including the 3rd row and
+    /// column of a perspective or 3D transform. To set a 3x3 [`Matrix`], convert it with
+    /// [`M44::from()`].
     ///
     /// - `matrix` matrix to copy, replacing existing [`Matrix`]
     ///
@@ -1050,7 +1507,8 @@ impl Canvas {
     }
 
     /// Sets [`Matrix`] to the identity matrix.
-    /// Any prior matrix state is overwritten.
+    /// Any prior matrix state is overwritten, including the 3rd row and column of a [`M44`]
+    /// that was applied with [`Self::concat_44()`] or [`Self::set_matrix()`].
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_resetMatrix>
     pub fn reset_matrix(&mut self) -> &mut Self {
@@ -1083,6 +1541,27 @@ impl Canvas {
         self
     }
 
+    /// Replaces clip with the intersection or difference of clip and `rect`, like
+    /// [`Self::clip_rect()`], and returns the resulting bounds of the clip in device coordinates.
+    ///
+    /// Returns `None` if the clip is empty afterwards, see [`Self::device_clip_bounds()`].
+    pub fn clip_rect_and_bounds(
+        &mut self,
+        rect: impl AsRef<Rect>,
+        op: impl Into<Option<ClipOp>>,
+        do_anti_alias: impl Into<Option<bool>>,
+    ) -> Option<IRect> {
+        let r = IRect::from_native_c(unsafe {
+            sb::C_SkCanvas_clipRectAndGetDeviceClipBounds(
+                self.native_mut(),
+                rect.as_ref().native(),
+                op.into().unwrap_or_default(),
+                do_anti_alias.into().unwrap_or_default(),
+            )
+        });
+        r.is_empty().if_false_some(r)
+    }
+
     pub fn clip_irect(
         &mut self,
         irect: impl AsRef<IRect>,
@@ -1176,7 +1655,66 @@ impl Canvas {
         self
     }
 
-    // quickReject() functions are implemented as a trait.
+    /// Returns `true` if [`Rect`] `rect`, transformed by [`Matrix`], can be quickly determined to
+    /// be outside of clip. May return `false` even though rect is outside of clip.
+    ///
+    /// Use to check if an area to be drawn is clipped out, to skip subsequent draw calls.
+    ///
+    /// - `rect` [`Rect`] to compare with clip
+    /// Returns `true` if `rect`, transformed by [`Matrix`], does not intersect clip
+    ///
+    /// example: <https://fiddle.skia.org/c/@Canvas_quickReject>
+    pub fn quick_reject_rect(&self, rect: impl AsRef<Rect>) -> bool {
+        unsafe { self.native().quickReject(rect.as_ref().native()) }
+    }
+
+    /// Returns `true` if [`IRect`] `rect`, transformed by [`Matrix`], can be quickly determined
+    /// to be outside of clip. See [`Self::quick_reject_rect()`].
+    pub fn quick_reject_irect(&self, rect: impl AsRef<IRect>) -> bool {
+        self.quick_reject_rect(Rect::from(*rect.as_ref()))
+    }
+
+    /// Returns `true` if `path`, transformed by [`Matrix`], can be quickly determined to be
+    /// outside of clip. May return `false` even though `path` is outside of clip.
+    ///
+    /// Use to check if an area to be drawn is clipped out, to skip subsequent draw calls.
+    ///
+    /// - `path` [`Path`] to compare with clip
+    /// Returns `true` if `path`, transformed by [`Matrix`], does not intersect clip
+    ///
+    /// example: <https://fiddle.skia.org/c/@Canvas_quickReject_2>
+    pub fn quick_reject_path(&self, path: &Path) -> bool {
+        unsafe { self.native().quickReject1(path.native()) }
+    }
+
+    /// Returns `true` if [`Region`] `device_rgn` does not intersect the bounds of clip.
+    /// May return `false` even though `device_rgn` is outside of clip.
+    ///
+    /// Like [`Self::clip_region()`], `device_rgn` is in device coordinates and not transformed by
+    /// [`Matrix`].
+    ///
+    /// - `device_rgn` [`Region`] to compare with clip
+    /// Returns `true` if the bounds of `device_rgn` do not intersect the device clip bounds
+    pub fn quick_reject_region(&self, device_rgn: &Region) -> bool {
+        match self.device_clip_bounds() {
+            Some(clip_bounds) => !device_rgn.intersects_rect(clip_bounds),
+            None => true,
+        }
+    }
+
+    /// Returns clip as [`Region`] in device coordinates, if it can be represented exactly by a
+    /// [`Region`], which is the case if clip is empty or a device aligned rectangle.
+    ///
+    /// Returns `None` for anti-aliased or complex clips.
+    pub fn device_clip_region(&self) -> Option<Region> {
+        if self.is_clip_empty() {
+            return Some(Region::new());
+        }
+        if self.is_clip_rect() {
+            return self.device_clip_bounds().map(Region::from_rect);
+        }
+        None
+    }
 
     /// Returns bounds of clip, transformed by inverse of [`Matrix`]. If clip is empty,
     /// return [`Rect::new_empty()`], where all [`Rect`] sides equal zero.
@@ -1188,10 +1726,18 @@ impl Canvas {
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_getLocalClipBounds>
     pub fn local_clip_bounds(&self) -> Option<Rect> {
-        let r = Rect::from_native_c(unsafe { sb::C_SkCanvas_getLocalClipBounds(self.native()) });
+        let r = self.local_clip_bounds_raw();
         r.is_empty().if_false_some(r)
     }
 
+    /// Returns bounds of clip, transformed by inverse of [`Matrix`], like
+    /// [`Self::local_clip_bounds()`], but without mapping empty bounds to `None`.
+    ///
+    /// Useful for inspecting degenerate bounds while debugging clip stacks.
+    pub fn local_clip_bounds_raw(&self) -> Rect {
+        Rect::from_native_c(unsafe { sb::C_SkCanvas_getLocalClipBounds(self.native()) })
+    }
+
     /// Returns [`IRect`] bounds of clip, unaffected by [`Matrix`]. If clip is empty,
     /// return [`Rect::new_empty()`], where all [`Rect`] sides equal zero.
     ///
@@ -1222,6 +1768,39 @@ impl Canvas {
         self
     }
 
+    /// Fills clip with color `color` that is specified in `color_space`.
+    /// `mode` determines how ARGB is combined with destination.
+    ///
+    /// Unlike [`Self::draw_color()`], which always interprets `color` as sRGB, this can be used
+    /// to specify colors outside of the sRGB gamut, for example on wide gamut canvases.
+    ///
+    /// - `color` [`Color4f`] representing unpremultiplied color
+    /// - `color_space` the [`ColorSpace`] of `color`; sRGB if `None`
+    /// - `mode` [`BlendMode`] used to combine source color and destination
+    pub fn draw_color_with_color_space(
+        &mut self,
+        color: impl Into<Color4f>,
+        color_space: Option<&ColorSpace>,
+        mode: impl Into<Option<BlendMode>>,
+    ) -> &mut Self {
+        let mut paint = Paint::new(color.into(), color_space);
+        paint.set_blend_mode(mode.into().unwrap_or_default());
+        self.draw_paint(&paint)
+    }
+
+    /// Fills clip with color `color` that is specified in `color_space` using
+    /// [`BlendMode::Src`]. See [`Self::draw_color_with_color_space()`].
+    ///
+    /// - `color` [`Color4f`] representing unpremultiplied color
+    /// - `color_space` the [`ColorSpace`] of `color`; sRGB if `None`
+    pub fn clear_with_color_space(
+        &mut self,
+        color: impl Into<Color4f>,
+        color_space: Option<&ColorSpace>,
+    ) -> &mut Self {
+        self.draw_color_with_color_space(color, color_space, BlendMode::Src)
+    }
+
     /// Fills clip with color `color` using [`BlendMode::Src`].
     /// This has the effect of replacing all pixels contained by clip with `color`.
     ///
@@ -1230,6 +1809,30 @@ impl Canvas {
         self.draw_color(color, BlendMode::Src)
     }
 
+    /// Sets every pixel of the top layer to the premultiplied, native byte order value `pmcolor`,
+    /// without any color conversion.
+    ///
+    /// Unlike [`Self::clear()`], this ignores the clip and writes the bits as they are, which is
+    /// useful for tests that compare exact pixel values. [`crate::pre_multiply_argb()`] can be
+    /// used to compute `pmcolor`.
+    ///
+    /// Returns `false` and leaves the canvas unchanged if its pixels are not accessible, or if the
+    /// top layer's color type is not [`ColorType::n32()`].
+    pub fn clear_raw_pmcolor(&mut self, pmcolor: PMColor) -> bool {
+        let layer = match self.access_top_layer_pixels() {
+            Some(layer) if layer.info.color_type() == ColorType::n32() => layer,
+            _ => return false,
+        };
+        let row_len = layer.info.min_row_bytes();
+        let bytes = pmcolor.to_ne_bytes();
+        for row in layer.pixels.chunks_mut(layer.row_bytes) {
+            for pixel in row[..row_len].chunks_exact_mut(bytes.len()) {
+                pixel.copy_from_slice(&bytes);
+            }
+        }
+        true
+    }
+
     /// Makes [`Canvas`] contents undefined. Subsequent calls that read [`Canvas`] pixels,
     /// such as drawing with [`BlendMode`], return undefined results. `discard()` does
     /// not change clip or [`Matrix`].
@@ -1258,6 +1861,20 @@ impl Canvas {
         self
     }
 
+    /// Fills the intersection of the clip and `rect` with [`Paint`] `paint`, as if the clip was
+    /// intersected with `rect` before [`Self::draw_paint()`]. The clip is left unchanged.
+    ///
+    /// Unlike [`Self::draw_rect()`], the [`crate::paint::Style`] of `paint` is ignored and `rect`
+    /// is always filled. `rect` is anti-aliased if `paint` is.
+    pub fn draw_paint_clipped(&mut self, rect: impl AsRef<Rect>, paint: &Paint) -> &mut Self {
+        {
+            let mut canvas = self.save_guard();
+            canvas.clip_rect(rect, None, paint.is_anti_alias());
+            canvas.draw_paint(paint);
+        }
+        self
+    }
+
     /// Draws `pts` using clip, [`Matrix`] and [`Paint`] `pain`.
     /// if the number of points is less than one, has no effect.
     /// `mode` may be one of: [`PointMode::Points`], [`PointMode::Lines`], or [`PointMode::Polygon`]
@@ -1288,6 +1905,9 @@ impl Canvas {
     ///
     /// example: <https://fiddle.skia.org/c/@Canvas_drawPoints>
     pub fn draw_points(&mut self, mode: PointMode, pts: &[Point], paint: &Paint) -> &mut Self {
+        if pts.is_empty() {
+            return self;
+        }
         unsafe {
             self.native_mut()
                 .drawPoints(mode, pts.len(), pts.native().as_ptr(), paint.native())
@@ -1295,17 +1915,72 @@ impl Canvas {
         self
     }
 
-    /// Draws point `p` using clip, [`Matrix`] and [`Paint`] paint.
+    /// Draws a series of connected lines through `pts` using clip, [`Matrix`] and [`Paint`]
+    /// `paint`.
     ///
-    /// The shape of point drawn depends on `paint` [`crate::paint::Cap`].
-    /// If `paint` is set to [`crate::paint::Cap::Round`], draw a circle of diameter [`Paint`]
-    /// stroke width. If `paint` is set to [`crate::paint::Cap::Square`] or
-    /// [`crate::paint::Cap::Butt`], draw a square of width and height [`Paint`] stroke width.
-    /// [`crate::paint::Style`] is ignored, as if were set to [`crate::paint::Style::Stroke`].
+    /// This is a convenience for [`Self::draw_points()`] in [`PointMode::Polygon`] mode. The
+    /// polyline is not closed, use [`Self::draw_path()`] for closed polygons.
     ///
-    /// - `p` top-left edge of circle or square
+    /// - `pts` the points of the polyline
     /// - `paint` stroke, blend, color, and so on, used to draw
-    pub fn draw_point(&mut self, p: impl Into<Point>, paint: &Paint) -> &mut Self {
+    pub fn draw_polyline(&mut self, pts: &[Point], paint: &Paint) -> &mut Self {
+        self.draw_points(PointMode::Polygon, pts, paint)
+    }
+
+    /// Draws independent line segments using clip, [`Matrix`] and [`Paint`] `paint`.
+    ///
+    /// All segments are drawn with a single call to [`Self::draw_points()`] in
+    /// [`PointMode::Lines`] mode. If the points are already stored pairwise in a slice, call
+    /// [`Self::draw_points()`] directly to avoid copying them.
+    ///
+    /// - `segments` start and end points of the line segments
+    /// - `paint` stroke, blend, color, and so on, used to draw
+    pub fn draw_lines(&mut self, segments: &[(Point, Point)], paint: &Paint) -> &mut Self {
+        let mut pts = Vec::with_capacity(segments.len() * 2);
+        for (p1, p2) in segments {
+            pts.push(*p1);
+            pts.push(*p2);
+        }
+        self.draw_points(PointMode::Lines, &pts, paint)
+    }
+
+    /// Draws a filled, anti-aliased circle of `radius` around each of the `centers` using clip,
+    /// [`Matrix`], and `color`.
+    ///
+    /// This is a convenience for [`Self::draw_points()`] in [`PointMode::Points`] mode: the size
+    /// of a point is the [`Paint`] stroke width, and its shape is determined by the
+    /// [`crate::paint::Cap`], so this draws with a [`crate::paint::Cap::Round`] stroke of width
+    /// `2 * radius`.
+    ///
+    /// - `centers` centers of the dots to draw
+    /// - `radius` half the diameter of each dot
+    /// - `color` color of the dots
+    pub fn draw_dots(
+        &mut self,
+        centers: &[Point],
+        radius: scalar,
+        color: impl Into<Color>,
+    ) -> &mut Self {
+        let mut paint = Paint::default();
+        paint
+            .set_color(color)
+            .set_anti_alias(true)
+            .set_stroke_cap(paint::Cap::Round)
+            .set_stroke_width(radius * 2.0);
+        self.draw_points(PointMode::Points, centers, &paint)
+    }
+
+    /// Draws point `p` using clip, [`Matrix`] and [`Paint`] paint.
+    ///
+    /// The shape of point drawn depends on `paint` [`crate::paint::Cap`].
+    /// If `paint` is set to [`crate::paint::Cap::Round`], draw a circle of diameter [`Paint`]
+    /// stroke width. If `paint` is set to [`crate::paint::Cap::Square`] or
+    /// [`crate::paint::Cap::Butt`], draw a square of width and height [`Paint`] stroke width.
+    /// [`crate::paint::Style`] is ignored, as if were set to [`crate::paint::Style::Stroke`].
+    ///
+    /// - `p` top-left edge of circle or square
+    /// - `paint` stroke, blend, color, and so on, used to draw
+    pub fn draw_point(&mut self, p: impl Into<Point>, paint: &Paint) -> &mut Self {
         let p = p.into();
         unsafe { self.native_mut().drawPoint(p.x, p.y, paint.native()) }
         self
@@ -1378,6 +2053,25 @@ impl Canvas {
         self
     }
 
+    /// Strokes the outline of [`Region`] `region` using clip, [`Matrix`], and [`Paint`] `paint`,
+    /// for example to visualize invalidated areas.
+    ///
+    /// The outline is drawn with one [`Path`], no matter how many rectangles the region consists
+    /// of. The style of `paint` is ignored, the stroke width, [`crate::paint::Join`], color,
+    /// blend, and so on are used.
+    ///
+    /// - `region` region to draw the outline of
+    /// - `paint` [`Paint`] stroke, blend, color, and so on, used to draw
+    pub fn draw_region_boundary(&mut self, region: &Region, paint: &Paint) -> &mut Self {
+        let mut path = Path::default();
+        if region.get_boundary_path(&mut path) {
+            let mut paint = paint.clone();
+            paint.set_style(paint::Style::Stroke);
+            self.draw_path(&path, &paint);
+        }
+        self
+    }
+
     /// Draws oval oval using clip, [`Matrix`], and [`Paint`].
     /// In `paint`: [`crate::paint::Style`] determines if oval is stroked or filled;
     /// if stroked, [`Paint`] stroke width describes the line thickness.
@@ -1447,6 +2141,31 @@ impl Canvas {
         self
     }
 
+    /// Draws a border of `width` along the inside of [`RRect`] `rrect`, using clip, [`Matrix`],
+    /// and [`Paint`] `paint`.
+    ///
+    /// The inner bounds are `rrect` inset by `width`, with its radii reduced accordingly, see
+    /// [`RRect::with_inset()`]. If `width` is so large that nothing remains inside, the whole
+    /// `rrect` is drawn.
+    ///
+    /// - `rrect` [`RRect`] outer bounds of the border
+    /// - `width` width of the border
+    /// - `paint` [`Paint`] stroke or fill, blend, color, and so on, used to draw
+    pub fn draw_rrect_border(
+        &mut self,
+        rrect: impl AsRef<RRect>,
+        width: scalar,
+        paint: &Paint,
+    ) -> &mut Self {
+        let outer = rrect.as_ref();
+        let inner = outer.with_inset((width, width));
+        if inner.is_empty() {
+            self.draw_rrect(outer, paint)
+        } else {
+            self.draw_drrect(outer, inner, paint)
+        }
+    }
+
     /// Draws circle at center with radius using clip, [`Matrix`], and [`Paint`] `paint`.
     /// If radius is zero or less, nothing is drawn.
     /// In `paint`: [`crate::paint::Style`] determines if circle is stroked or filled;
@@ -1556,22 +2275,26 @@ impl Canvas {
         self
     }
 
-    pub fn draw_image(
+    /// Draws [`Image`] `image` with its top-left corner at `left_top`, using the default
+    /// [`SamplingOptions`]. See [`Self::draw_image_with_sampling_options()`].
+    pub fn draw_image<'a>(
         &mut self,
         image: impl AsRef<Image>,
         left_top: impl Into<Point>,
-        paint: Option<&Paint>,
+        paint: impl Into<Option<&'a Paint>>,
     ) -> &mut Self {
         let left_top = left_top.into();
         self.draw_image_with_sampling_options(image, left_top, SamplingOptions::default(), paint)
     }
 
-    pub fn draw_image_rect(
+    /// Draws [`Rect`] `src` of [`Image`] `image` into [`Rect`] `dst`, using the default
+    /// [`SamplingOptions`]. See [`Self::draw_image_rect_with_sampling_options()`].
+    pub fn draw_image_rect<'a>(
         &mut self,
         image: impl AsRef<Image>,
         src: Option<(&Rect, SrcRectConstraint)>,
         dst: impl AsRef<Rect>,
-        paint: &Paint,
+        paint: impl Into<Option<&'a Paint>>,
     ) -> &mut Self {
         self.draw_image_rect_with_sampling_options(
             image,
@@ -1582,14 +2305,69 @@ impl Canvas {
         )
     }
 
-    pub fn draw_image_with_sampling_options(
+    /// Draws [`Rect`] `src` of [`Image`] `image` into [`Rect`] `dst`, sampling only from inside
+    /// of `src`. See [`Self::draw_image_rect()`] and [`SrcRectConstraint::Strict`].
+    ///
+    /// This is the safe default for drawing parts of a texture atlas or sprite sheet, because
+    /// pixels next to `src` never bleed into the result.
+    pub fn draw_image_rect_strict<'a>(
+        &mut self,
+        image: impl AsRef<Image>,
+        src: impl AsRef<Rect>,
+        dst: impl AsRef<Rect>,
+        paint: impl Into<Option<&'a Paint>>,
+    ) -> &mut Self {
+        self.draw_image_rect(
+            image,
+            Some((src.as_ref(), SrcRectConstraint::Strict)),
+            dst,
+            paint,
+        )
+    }
+
+    /// Draws [`Rect`] `src` of [`Image`] `image` into [`Rect`] `dst`, allowing the sampling to
+    /// read pixels outside of `src`. See [`Self::draw_image_rect()`] and
+    /// [`SrcRectConstraint::Fast`].
+    ///
+    /// This may be faster on the GPU, but when filtering, pixels next to `src` can bleed into the
+    /// result, for example at the boundaries of the entries of a texture atlas. Use
+    /// [`Self::draw_image_rect_strict()`] if that matters.
+    pub fn draw_image_rect_fast<'a>(
+        &mut self,
+        image: impl AsRef<Image>,
+        src: impl AsRef<Rect>,
+        dst: impl AsRef<Rect>,
+        paint: impl Into<Option<&'a Paint>>,
+    ) -> &mut Self {
+        self.draw_image_rect(
+            image,
+            Some((src.as_ref(), SrcRectConstraint::Fast)),
+            dst,
+            paint,
+        )
+    }
+
+    /// Draws [`Image`] `image` with its top-left corner at `left_top`, using `sampling` to choose
+    /// how pixels are filtered.
+    ///
+    /// Sampling is specified explicitly and not by the filter quality of [`Paint`], so that
+    /// images drawn at a point and images drawn into a [`Rect`] with
+    /// [`Self::draw_image_rect_with_sampling_options()`] are filtered the same way.
+    ///
+    /// - `image` [`Image`] containing pixels, dimensions, and format
+    /// - `left_top` position of the image's top-left corner
+    /// - `sampling` [`SamplingOptions`] used when sampling from the image
+    /// - `paint` [`Paint`] containing [`BlendMode`], [`crate::ColorFilter`], [`ImageFilter`],
+    ///   and so on; or `None`
+    pub fn draw_image_with_sampling_options<'a>(
         &mut self,
         image: impl AsRef<Image>,
         left_top: impl Into<Point>,
         sampling: impl Into<SamplingOptions>,
-        paint: Option<&Paint>,
+        paint: impl Into<Option<&'a Paint>>,
     ) -> &mut Self {
         let left_top = left_top.into();
+        let paint = paint.into();
         unsafe {
             self.native_mut().drawImage(
                 image.as_ref().native(),
@@ -1602,15 +2380,28 @@ impl Canvas {
         self
     }
 
-    pub fn draw_image_rect_with_sampling_options(
+    /// Draws [`Rect`] `src` of [`Image`] `image`, scaled and translated to fill [`Rect`] `dst`,
+    /// using `sampling` to choose how pixels are filtered, for example
+    /// [`crate::CubicResampler::mitchell()`] for high quality downscaling.
+    ///
+    /// If `src` is `None`, the whole image is drawn.
+    ///
+    /// - `image` [`Image`] containing pixels, dimensions, and format
+    /// - `src` source [`Rect`] of image to draw from, and how to sample close to its edges
+    /// - `dst` destination [`Rect`] of image to draw to
+    /// - `sampling` [`SamplingOptions`] used when sampling from the image
+    /// - `paint` [`Paint`] containing [`BlendMode`], [`crate::ColorFilter`], [`ImageFilter`],
+    ///   and so on; or `None`
+    pub fn draw_image_rect_with_sampling_options<'a>(
         &mut self,
         image: impl AsRef<Image>,
         src: Option<(&Rect, SrcRectConstraint)>,
         dst: impl AsRef<Rect>,
         sampling: impl Into<SamplingOptions>,
-        paint: &Paint,
+        paint: impl Into<Option<&'a Paint>>,
     ) -> &mut Self {
         let sampling = sampling.into();
+        let paint = paint.into();
         match src {
             Some((src, constraint)) => unsafe {
                 self.native_mut().drawImageRect(
@@ -1618,7 +2409,7 @@ impl Canvas {
                     src.native(),
                     dst.as_ref().native(),
                     sampling.native(),
-                    paint.native(),
+                    paint.native_ptr_or_null(),
                     constraint,
                 )
             },
@@ -1627,13 +2418,139 @@ impl Canvas {
                     image.as_ref().native(),
                     dst.as_ref().native(),
                     sampling.native(),
-                    paint.native(),
+                    paint.native_ptr_or_null(),
                 )
             },
         }
         self
     }
 
+    /// Draws [`IRect`] `src` of [`Image`] `image` into the pixel aligned [`IRect`] `dst`. If `src`
+    /// is `None`, the whole image is drawn.
+    ///
+    /// If `src` and `dst` have the same dimensions, the pixels are copied without filtering and
+    /// `sampling` is ignored, so that blits of tiles stay sharp. Otherwise `src` is scaled into
+    /// `dst` with `sampling`, without reading pixels outside of `src`
+    /// ([`SrcRectConstraint::Strict`]).
+    ///
+    /// Pixel alignment is only kept if the current transform is an integer translation.
+    pub fn draw_image_irect<'a>(
+        &mut self,
+        image: impl AsRef<Image>,
+        src: Option<&IRect>,
+        dst: impl AsRef<IRect>,
+        sampling: impl Into<SamplingOptions>,
+        paint: impl Into<Option<&'a Paint>>,
+    ) -> &mut Self {
+        let image = image.as_ref();
+        let src = src.copied().unwrap_or_else(|| image.bounds());
+        let dst = dst.as_ref();
+        let sampling = if src.size() == dst.size() {
+            SamplingOptions::default()
+        } else {
+            sampling.into()
+        };
+        self.draw_image_rect_with_sampling_options(
+            image,
+            Some((&Rect::from(src), SrcRectConstraint::Strict)),
+            Rect::from(*dst),
+            sampling,
+            paint,
+        )
+    }
+
+    /// Fills `rect` with `color`, with anti-aliasing controlled per edge by `aa_flags`. If `clip`
+    /// is not `None`, it is a quad inside `rect` that clips the fill.
+    ///
+    /// This is useful for tiled fills, where edges shared by adjacent tiles must not be
+    /// anti-aliased to avoid seams.
+    ///
+    /// - `rect` [`Rect`] to fill
+    /// - `clip` optional quad that clips `rect`, in clockwise order
+    /// - `aa_flags` which edges are anti-aliased
+    /// - `color` [`Color4f`] representing unpremultiplied color
+    /// - `mode` [`BlendMode`] used to combine source color and destination
+    pub fn draw_edge_aa_quad(
+        &mut self,
+        rect: impl AsRef<Rect>,
+        clip: Option<&[Point; 4]>,
+        aa_flags: QuadAAFlags,
+        color: impl Into<Color4f>,
+        mode: BlendMode,
+    ) -> &mut Self {
+        unsafe {
+            self.native_mut().experimental_DrawEdgeAAQuad(
+                rect.as_ref().native(),
+                clip.map(|c| c[..].native().as_ptr()).unwrap_or(ptr::null()),
+                aa_flags.bits(),
+                &color.into().into_native(),
+                mode,
+            )
+        }
+        self
+    }
+
+    /// Draws a set of images, each into its own destination [`Rect`], with anti-aliasing
+    /// controlled per edge. This is more efficient than drawing the images one by one, for example
+    /// when compositing tiles.
+    ///
+    /// - `set` the images and their placements
+    /// - `dst_clips` 4 points for each entry that has [`ImageSetEntry::has_clip`] set, in order;
+    ///   each quad clips the entry's destination
+    /// - `pre_view_matrices` matrices that are referenced by [`ImageSetEntry::matrix_index`] and
+    ///   are applied before the canvas' [`Matrix`]
+    /// - `sampling` [`SamplingOptions`] used when sampling from the images
+    /// - `paint` [`Paint`] containing [`BlendMode`], [`crate::ColorFilter`], [`ImageFilter`],
+    ///   and so on; or `None`
+    /// - `constraint` whether sampling is restricted to each entry's `src_rect`
+    ///
+    /// Panics if the number of `dst_clips` does not match the entries that have a clip, or if an
+    /// entry's matrix index is out of bounds.
+    pub fn draw_edge_aa_image_set<'a>(
+        &mut self,
+        set: &[ImageSetEntry],
+        dst_clips: &[Point],
+        pre_view_matrices: &[Matrix],
+        sampling: impl Into<SamplingOptions>,
+        paint: impl Into<Option<&'a Paint>>,
+        constraint: impl Into<Option<SrcRectConstraint>>,
+    ) -> &mut Self {
+        assert_eq!(
+            set.iter().filter(|e| e.has_clip).count() * 4,
+            dst_clips.len()
+        );
+        assert!(set
+            .iter()
+            .filter_map(|e| e.matrix_index)
+            .all(|i| i < pre_view_matrices.len()));
+
+        // `set` stays borrowed until the entries are dropped.
+        let entries: Vec<_> = set.iter().map(|e| e.native_unshared()).collect();
+        let dst_clips = if dst_clips.is_empty() {
+            ptr::null()
+        } else {
+            dst_clips.native().as_ptr()
+        };
+        let pre_view_matrices = if pre_view_matrices.is_empty() {
+            ptr::null()
+        } else {
+            pre_view_matrices.native().as_ptr()
+        };
+
+        unsafe {
+            self.native_mut().experimental_DrawEdgeAAImageSet(
+                entries.as_ptr(),
+                entries.len().try_into().unwrap(),
+                dst_clips,
+                pre_view_matrices,
+                sampling.into().native(),
+                paint.into().native_ptr_or_null(),
+                constraint.into().unwrap_or(SrcRectConstraint::Strict),
+            )
+        }
+        self
+    }
+
     /// Draws [`Image`] `image` stretched proportionally to fit into [`Rect`] `dst`.
     /// [`IRect`] `center` divides the image into nine sections: four sides, four corners, and
     /// the center. Corners are unmodified or scaled down proportionately if their sides
@@ -1650,10 +2567,17 @@ impl Canvas {
     /// as [`Shader`] made from [`RCHandle<Image>::to_shader()`] with [`crate::TileMode::Clamp`] set
     /// replicates the image edge color when it samples outside of its bounds.
     ///
+    /// The divisions between the nine sections are not snapped to device pixels. Each section
+    /// is drawn into its own fractional [`Rect`], which the raster backend rounds to whole pixels,
+    /// so a `dst` that moves by fractions of a pixel can change the size of the sections by one
+    /// pixel from frame to frame. To avoid that, map `dst` to device coordinates and round it,
+    /// for example with [`Rect::round()`], before drawing.
+    ///
     /// - `image` [`Image`] containing pixels, dimensions, and format
     /// - `center` [`IRect`] edge of image corners and sides
     /// - `dst` destination [`Rect`] of image to draw to
-    /// - `filter` what technique to use when sampling the image
+    /// - `filter_mode` what technique to use when sampling the image; corners are drawn
+    ///   unscaled if they fit, so [`FilterMode::Linear`] only affects the sides and the center
     /// - `paint` [`Paint`] containing [`BlendMode`], [`crate::ColorFilter`], [`ImageFilter`],
     ///    and so on; or `None`
     pub fn draw_image_nine(
@@ -1722,7 +2646,67 @@ impl Canvas {
         self
     }
 
-    // TODO: drawSimpleText?
+    /// Draws [`Bitmap`] `bitmap` stretched proportionally to fit into [`Rect`] `dst`, divided
+    /// by [`lattice::Lattice`] `lattice`. See [`Self::draw_image_lattice()`].
+    ///
+    /// The pixels of `bitmap` are shared with the [`Image`] that is drawn if `bitmap` is
+    /// immutable, otherwise they are copied.
+    ///
+    /// - `bitmap` [`Bitmap`] containing pixels, dimensions, and format
+    /// - `lattice` division of bitmap into fixed and variable rectangles
+    /// - `dst` destination [`Rect`] of bitmap to draw to
+    /// - `filter` what technique to use when sampling the bitmap
+    /// - `paint` [`Paint`] containing [`BlendMode`], [`crate::ColorFilter`], [`ImageFilter`],
+    /// and so on; or `None`
+    pub fn draw_bitmap_lattice(
+        &mut self,
+        bitmap: &Bitmap,
+        lattice: &Lattice,
+        dst: impl AsRef<Rect>,
+        filter: FilterMode,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        self.draw_image_lattice(bitmap.as_image(), lattice, dst, filter, paint)
+    }
+
+    /// Draws `text`, with origin at `(origin.x, origin.y)`, using clip, [`Matrix`], [`Font`]
+    /// `font`, and [`Paint`] `paint`.
+    ///
+    /// `text` is interpreted according to `encoding`, which may be UTF-8, UTF-16, UTF-32, or
+    /// glyph ids. Glyph ids and UTF-16 and UTF-32 code units are expected in native byte order.
+    ///
+    /// This function uses the default character-to-glyph mapping from the [`crate::Typeface`] in
+    /// font.  It does not perform typeface fallback for characters not found in the
+    /// [`crate::Typeface`].  It does not perform kerning; glyphs are positioned based on their
+    /// default advances.
+    ///
+    /// - `text` character code points or glyphs drawn
+    /// - `encoding` text encoding used in the text array
+    /// - `origin` start of text on x,y-axis
+    /// - `font` typeface, text size and so, used to describe the text
+    /// - `paint` blend, color, and so on, used to draw
+    pub fn draw_simple_text(
+        &mut self,
+        text: &[u8],
+        encoding: TextEncoding,
+        origin: impl Into<Point>,
+        font: &Font,
+        paint: &Paint,
+    ) -> &mut Self {
+        let origin = origin.into();
+        unsafe {
+            self.native_mut().drawSimpleText(
+                text.as_ptr() as _,
+                text.len(),
+                encoding.into_native(),
+                origin.x,
+                origin.y,
+                font.native(),
+                paint.native(),
+            )
+        }
+        self
+    }
 
     /// Draws [`String`], with origin at `(origin.x, origin.y)`, using clip, [`Matrix`], [`Font`]
     /// `font`, and [`Paint`] `paint`.
@@ -1752,20 +2736,13 @@ impl Canvas {
     ) -> &mut Self {
         // rust specific, based on drawSimpleText with fixed UTF8 encoding,
         // implementation is similar to Font's *_str methods.
-        let origin = origin.into();
-        let bytes = str.as_ref().as_bytes();
-        unsafe {
-            self.native_mut().drawSimpleText(
-                bytes.as_ptr() as _,
-                bytes.len(),
-                TextEncoding::UTF8.into_native(),
-                origin.x,
-                origin.y,
-                font.native(),
-                paint.native(),
-            )
-        }
-        self
+        self.draw_simple_text(
+            str.as_ref().as_bytes(),
+            TextEncoding::UTF8,
+            origin,
+            font,
+            paint,
+        )
     }
 
     /// Draws glyphs at positions relative to `origin` styled with `font` and `paint` with
@@ -1955,6 +2932,32 @@ impl Canvas {
         self
     }
 
+    /// Draws the part of [`Picture`] `picture` that is inside of `cull`, like
+    /// [`Self::draw_picture()`].
+    ///
+    /// `cull` is in the coordinates of the picture, so it is transformed by `matrix`, if
+    /// provided. The clip and [`Matrix`] of [`Canvas`] are restored afterwards.
+    ///
+    /// - `picture` recorded drawing commands to play
+    /// - `cull` the part of the picture to draw
+    /// - `matrix` [`Matrix`] to rotate, scale, translate, and so on; may be `None`
+    /// - `paint` [`Paint`] to apply transparency, filtering, and so on; may be `None`
+    pub fn draw_picture_clipped(
+        &mut self,
+        picture: impl AsRef<Picture>,
+        cull: impl AsRef<Rect>,
+        matrix: Option<&Matrix>,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let save_count = self.save();
+        if let Some(matrix) = matrix {
+            self.concat(matrix);
+        }
+        self.clip_rect(cull, None, None)
+            .draw_picture(picture, None, paint)
+            .restore_to_count(save_count)
+    }
+
     /// Draws [`Vertices`] vertices, a triangle mesh, using clip and [`Matrix`].
     /// If `paint` contains an [`Shader`] and vertices does not contain tex coords, the shader is
     /// mapped using the vertices' positions.
@@ -2041,7 +3044,55 @@ impl Canvas {
         self
     }
 
-    // TODO: drawAtlas
+    /// Draws a set of sprites from `atlas`, using clip, [`Matrix`], and optional [`Paint`] paint.
+    /// paint uses anti-alias, alpha, [`crate::ColorFilter`], [`ImageFilter`], and [`BlendMode`]
+    /// to draw, if present. For each entry in the array, [`Rect`] `tex` locates sprite in
+    /// `atlas`, and [`RSXform`] `xform` transforms it into destination space.
+    ///
+    /// `xform`, `tex`, and `colors` if present, must contain the same number of entries.
+    /// Optional `colors` are applied for each sprite using [`BlendMode`] mode, treating sprite as
+    /// source and colors as destination.
+    /// Optional `cull_rect` is a conservative bounds of all transformed sprites.
+    /// If `cull_rect` is outside of clip, canvas can skip drawing.
+    ///
+    /// - `atlas` [`Image`] containing sprites
+    /// - `xform` [`RSXform`] mappings for sprites in `atlas`
+    /// - `tex` [`Rect`] locations of sprites in `atlas`
+    /// - `colors` one per sprite, blended with sprite using [`BlendMode`]; may be `None`
+    /// - `mode` [`BlendMode`] combining colors and sprites
+    /// - `sampling` [`SamplingOptions`] used when sampling from the atlas image
+    /// - `cull_rect` bounds of transformed sprites for efficient clipping; may be `None`
+    /// - `paint` [`crate::ColorFilter`], [`ImageFilter`], [`BlendMode`], and so on; may be `None`
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_atlas(
+        &mut self,
+        atlas: &Image,
+        xform: &[RSXform],
+        tex: &[Rect],
+        colors: Option<&[Color]>,
+        mode: BlendMode,
+        sampling: impl Into<SamplingOptions>,
+        cull_rect: Option<&Rect>,
+        paint: Option<&Paint>,
+    ) -> &mut Self {
+        let count = xform.len();
+        assert_eq!(tex.len(), count);
+        assert!(colors.map(|c| c.len() == count).unwrap_or(true));
+        unsafe {
+            self.native_mut().drawAtlas(
+                atlas.native(),
+                xform.native().as_ptr(),
+                tex.native().as_ptr(),
+                colors.map(|c| c.native().as_ptr()).unwrap_or(ptr::null()),
+                count.try_into().unwrap(),
+                mode,
+                sampling.into().native(),
+                cull_rect.native_ptr_or_null(),
+                paint.native_ptr_or_null(),
+            )
+        }
+        self
+    }
 
     /// Draws [`Drawable`] drawable using clip and [`Matrix`], concatenated with
     /// optional matrix.
@@ -2129,6 +3180,19 @@ impl Canvas {
         unsafe { sb::C_SkCanvas_isClipRect(self.native()) }
     }
 
+    /// Returns whether clip is empty, a rectangle, or something more complex.
+    ///
+    /// Useful to assert that layouts do not accumulate path clips.
+    pub fn clip_kind(&self) -> ClipKind {
+        if self.is_clip_empty() {
+            ClipKind::Empty
+        } else if self.is_clip_rect() {
+            ClipKind::Rect
+        } else {
+            ClipKind::Complex
+        }
+    }
+
     /// Returns the current transform from local coordinates to the 'device', which for most
     /// purposes means pixels.
     ///
@@ -2138,10 +3202,33 @@ impl Canvas {
     }
 
     /// Throws away the 3rd row and column in the matrix, so be warned.
+    ///
+    /// The matrix is returned by value and does not borrow the canvas.
     pub fn local_to_device_as_3x3(&self) -> Matrix {
         self.local_to_device().to_m33()
     }
 
+    /// Maps `device` from device coordinates to the local coordinates of the current transform,
+    /// for example to hit test pointer positions against shapes drawn in local coordinates.
+    ///
+    /// Like [`Self::local_to_device_as_3x3()`], this ignores the 3rd row and column of the
+    /// transform.
+    ///
+    /// Returns `None` if the transform is not invertible, for example after a zero scale.
+    pub fn map_point_to_local(&self, device: impl Into<Point>) -> Option<Point> {
+        self.local_to_device_as_3x3()
+            .invert()
+            .map(|inverse| inverse.map_point(device))
+    }
+
+    /// Maps `local` with the current transform and returns the device space bounds of the result.
+    ///
+    /// Like [`Self::local_to_device_as_3x3()`], this ignores the 3rd row and column of the
+    /// transform.
+    pub fn map_rect_to_device(&self, local: impl AsRef<Rect>) -> Rect {
+        self.local_to_device_as_3x3().map_rect(local).0
+    }
+
     /// DEPRECATED
     /// Legacy version of [`Self::local_to_device()`], which strips away any Z information, and just
     /// returns a 3x3 version.
@@ -2184,33 +3271,27 @@ impl Canvas {
     }
 }
 
+// The `QuickReject` trait implementations are kept for backwards compatibility, prefer the
+// inherent `quick_reject_*` functions.
+
 impl QuickReject<Rect> for Canvas {
-    /// Returns `true` if [`Rect`] `rect`, transformed by [`Matrix`], can be quickly determined to
-    /// be outside of clip. May return `false` even though rect is outside of clip.
-    ///
-    /// Use to check if an area to be drawn is clipped out, to skip subsequent draw calls.
-    ///
-    /// - `rect` [`Rect`] to compare with clip
-    /// Returns `true` if `rect`, transformed by [`Matrix`], does not intersect clip
-    ///
-    /// example: <https://fiddle.skia.org/c/@Canvas_quickReject>
+    /// See [`Canvas::quick_reject_rect()`].
     fn quick_reject(&self, rect: &Rect) -> bool {
-        unsafe { self.native().quickReject(rect.native()) }
+        self.quick_reject_rect(rect)
+    }
+}
+
+impl QuickReject<IRect> for Canvas {
+    /// See [`Canvas::quick_reject_irect()`].
+    fn quick_reject(&self, rect: &IRect) -> bool {
+        self.quick_reject_irect(rect)
     }
 }
 
 impl QuickReject<Path> for Canvas {
-    /// Returns `true` if `path`, transformed by [`Matrix`], can be quickly determined to be
-    /// outside of clip. May return `false` even though `path` is outside of clip.
-    ///
-    /// Use to check if an area to be drawn is clipped out, to skip subsequent draw calls.
-    ///
-    /// - `path` [`Path`] to compare with clip
-    /// Returns `true` if `path`, transformed by [`Matrix`], does not intersect clip
-    ///
-    /// example: <https://fiddle.skia.org/c/@Canvas_quickReject_2>
+    /// See [`Canvas::quick_reject_path()`].
     fn quick_reject(&self, path: &Path) -> bool {
-        unsafe { self.native().quickReject1(path.native()) }
+        self.quick_reject_path(path)
     }
 }
 
@@ -2348,12 +3429,15 @@ impl<'a> AutoRestoredCanvas<'a> {
 pub enum AutoCanvasRestore {}
 
 impl AutoCanvasRestore {
-    // TODO: rename to save(), add a method to Canvas, perhaps named auto_restored()?
+    // TODO: rename to save()?
     /// Preserves [`Canvas::save()`] count. Optionally saves [`Canvas`] clip and [`Canvas`] matrix.
     ///
     /// - `canvas` [`Canvas`] to guard
     /// - `do_save` call [`Canvas::save()`]
     /// Returns utility to restore [`Canvas`] state on destructor
+    ///
+    /// The guard borrows the canvas mutably, so while the guard is alive, the canvas can only be
+    /// accessed through it.
     pub fn guard(canvas: &mut Canvas, do_save: bool) -> AutoRestoredCanvas {
         let restore = construct(|acr| unsafe {
             sb::C_SkAutoCanvasRestore_Construct(acr, canvas.native_mut(), do_save)
@@ -2366,8 +3450,11 @@ impl AutoCanvasRestore {
 #[cfg(test)]
 mod tests {
     use crate::{
-        canvas::SaveLayerFlags, canvas::SaveLayerRec, AlphaType, Canvas, ClipOp, Color, ColorType,
-        ImageInfo, OwnedCanvas, Rect,
+        canvas, canvas::Lattice, canvas::SaveLayerFlags, canvas::SaveLayerRec, image_filters,
+        vertices, AlphaType, AutoCanvasRestore, Bitmap, BlendMode, Canvas, ClipOp, Color,
+        ColorType, CubicResampler, Data, FilterMode, Font, IRect, ImageInfo, Matrix, OwnedCanvas,
+        Paint, Path, PictureRecorder, PixelGeometry, Pixmap, Point, RSXform, Rect, SamplingOptions,
+        TextBlob, Typeface, Vertices, M44, V4,
     };
 
     #[test]
@@ -2386,6 +3473,47 @@ mod tests {
         assert_eq!(0xff, bytes[3]);
     }
 
+    #[test]
+    fn test_raster_direct_with_padded_row_bytes() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let row_bytes = info.min_row_bytes() + 16;
+        let mut bytes = vec![0u8; row_bytes * 2];
+        {
+            let mut canvas =
+                Canvas::from_raster_direct(&info, bytes.as_mut(), row_bytes, None).unwrap();
+            canvas.clear(Color::RED);
+
+            // reading back with the same stride must yield the same layout.
+            let mut read_back = vec![0u8; row_bytes * 2];
+            assert!(canvas.read_pixels(&info, &mut read_back, row_bytes, (0, 0)));
+            assert_eq!(&read_back[..4], &[0xff, 0x00, 0x00, 0xff]);
+            assert_eq!(
+                &read_back[row_bytes..row_bytes + 4],
+                &[0xff, 0x00, 0x00, 0xff]
+            );
+        }
+
+        // first and second row start at the strided offsets.
+        assert_eq!(&bytes[..4], &[0xff, 0x00, 0x00, 0xff]);
+        assert_eq!(&bytes[row_bytes..row_bytes + 4], &[0xff, 0x00, 0x00, 0xff]);
+        assert_eq!(
+            &bytes[row_bytes + 4..row_bytes + 8],
+            &[0xff, 0x00, 0x00, 0xff]
+        );
+        // the padding in between is left untouched.
+        assert!(bytes[info.min_row_bytes()..row_bytes]
+            .iter()
+            .all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_raster_direct_rejects_too_small_buffer_for_padded_row_bytes() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let row_bytes = info.min_row_bytes() + 16;
+        let mut bytes = vec![0u8; info.compute_byte_size(row_bytes) - 1];
+        assert!(Canvas::from_raster_direct(&info, bytes.as_mut(), row_bytes, None).is_none());
+    }
+
     #[test]
     fn test_raster_direct_n32_creation_and_clear_in_memory() {
         let mut pixels: [u32; 4] = Default::default();
@@ -2399,7 +3527,315 @@ mod tests {
     }
 
     #[test]
-    fn test_empty_canvas_creation() {
+    fn test_clear_raw_pmcolor_writes_exact_bits() {
+        let pmcolor = crate::pre_multiply_argb(0x80, 0xff, 0x40, 0x00);
+        let mut pixels: [u32; 4] = Default::default();
+        {
+            let mut canvas = Canvas::from_raster_direct_n32((2, 2), pixels.as_mut(), None).unwrap();
+            canvas.clip_rect(Rect::from_wh(1.0, 1.0), None, None);
+            assert!(canvas.clear_raw_pmcolor(pmcolor));
+        }
+        assert_eq!(pixels, [pmcolor; 4]);
+
+        let mut recorder = PictureRecorder::new();
+        let recording_canvas = recorder.begin_recording(Rect::from_wh(2.0, 2.0), None);
+        assert!(!recording_canvas.clear_raw_pmcolor(pmcolor));
+    }
+
+    #[test]
+    fn test_image_snapshot_of_raster_direct_canvas() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut bytes = vec![0u8; info.compute_min_byte_size()];
+        let mut canvas = Canvas::from_raster_direct(&info, bytes.as_mut(), None, None).unwrap();
+        canvas.clear(Color::RED);
+        let image = canvas.image_snapshot().unwrap();
+        assert_eq!(image.dimensions(), (2, 2).into());
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((1, 1)), Color::RED);
+    }
+
+    #[test]
+    fn test_peek_pixels_of_cleared_canvas() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut bytes = vec![0u8; info.compute_min_byte_size()];
+        let mut canvas = Canvas::from_raster_direct(&info, bytes.as_mut(), None, None).unwrap();
+        canvas.clear(Color::BLUE);
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.dimensions(), (2, 2).into());
+        assert_eq!(pixmap.get_color((0, 0)), Color::BLUE);
+    }
+
+    #[test]
+    fn test_peek_pixels_of_canvas_without_pixels() {
+        let mut canvas = Canvas::new((2, 2), None).unwrap();
+        assert!(canvas.peek_pixels().is_none());
+    }
+
+    #[test]
+    fn test_write_pixels_from_pixmap() {
+        let info = ImageInfo::new((2, 2), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut surface = crate::Surface::new_raster(&info, None, None).unwrap();
+        let canvas = surface.canvas();
+
+        let pixels = [0xffu8, 0x00, 0x00, 0xff].repeat(4);
+        let pixmap = Pixmap::new(&info, &pixels, info.min_row_bytes());
+        assert!(canvas.write_pixels_from_pixmap(&pixmap, (0, 0)));
+        assert_eq!(canvas.peek_pixels().unwrap().get_color((1, 1)), Color::RED);
+    }
+
+    #[test]
+    fn test_write_pixels_from_empty_pixmap() {
+        let mut canvas = OwnedCanvas::default();
+        assert!(!canvas.write_pixels_from_pixmap(&Pixmap::default(), (0, 0)));
+    }
+
+    #[test]
+    fn test_draw_image_lattice() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((6, 6)).unwrap();
+        image_surface.canvas().clear(Color::GREEN);
+        let image = image_surface.image_snapshot();
+
+        let lattice = Lattice {
+            x_divs: &[2, 4],
+            y_divs: &[2, 4],
+            rect_types: None,
+            bounds: None,
+            colors: None,
+        };
+
+        let mut surface = crate::Surface::new_raster_n32_premul((30, 30)).unwrap();
+        let canvas = surface.canvas();
+        canvas.draw_image_lattice(
+            &image,
+            &lattice,
+            Rect::from_wh(30.0, 30.0),
+            FilterMode::Nearest,
+            None,
+        );
+        assert_eq!(
+            canvas.peek_pixels().unwrap().get_color((15, 15)),
+            Color::GREEN
+        );
+    }
+
+    #[test]
+    fn test_draw_bitmap_lattice() {
+        let mut bitmap = Bitmap::new();
+        bitmap.alloc_n32_pixels((6, 6), None);
+        bitmap.erase_color(Color::RED);
+        bitmap.erase(Color::BLUE, IRect::from_ltrb(2, 2, 4, 4));
+        bitmap.set_immutable();
+
+        let lattice = Lattice {
+            x_divs: &[2, 4],
+            y_divs: &[2, 4],
+            rect_types: None,
+            bounds: None,
+            colors: None,
+        };
+
+        let mut surface = crate::Surface::new_raster_n32_premul((30, 30)).unwrap();
+        let canvas = surface.canvas();
+        canvas.draw_bitmap_lattice(
+            &bitmap,
+            &lattice,
+            Rect::from_wh(30.0, 30.0),
+            FilterMode::Nearest,
+            None,
+        );
+        let pixmap = canvas.peek_pixels().unwrap();
+        // fixed corners keep their size, the center stretches.
+        assert_eq!(pixmap.get_color((1, 1)), Color::RED);
+        assert_eq!(pixmap.get_color((15, 15)), Color::BLUE);
+        assert_eq!(pixmap.get_color((28, 28)), Color::RED);
+    }
+
+    #[test]
+    fn test_draw_patch_without_tex_coords() {
+        let cubics = [
+            Point::new(0.0, 0.0),
+            Point::new(7.0, 0.0),
+            Point::new(13.0, 0.0),
+            Point::new(20.0, 0.0),
+            Point::new(20.0, 7.0),
+            Point::new(20.0, 13.0),
+            Point::new(20.0, 20.0),
+            Point::new(13.0, 20.0),
+            Point::new(7.0, 20.0),
+            Point::new(0.0, 20.0),
+            Point::new(0.0, 13.0),
+            Point::new(0.0, 7.0),
+        ];
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        let mut paint = Paint::default();
+        paint.set_color(Color::BLUE);
+
+        // only the interpolated colors
+        canvas.draw_patch(&cubics, &[Color::RED; 4], None, BlendMode::Dst, &paint);
+        assert_eq!(
+            canvas.peek_pixels().unwrap().get_color((10, 10)),
+            Color::RED
+        );
+
+        // neither colors nor texture coordinates
+        canvas.draw_patch(&cubics, None, None, BlendMode::Dst, &paint);
+        assert_eq!(
+            canvas.peek_pixels().unwrap().get_color((10, 10)),
+            Color::BLUE
+        );
+    }
+
+    #[test]
+    fn test_draw_drawable() {
+        let mut recorder = PictureRecorder::new();
+        let recording_canvas = recorder.begin_recording(Rect::from_wh(4.0, 4.0), None);
+        recording_canvas.draw_rect(Rect::from_wh(4.0, 4.0), &Paint::default());
+        let mut drawable = recorder.finish_recording_as_drawable().unwrap();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_drawable(&mut drawable, Some(&Matrix::translate((4.0, 4.0))));
+        canvas.draw_drawable_at(&mut drawable, (12, 12));
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::BLACK);
+        assert_eq!(pixmap.get_color((13, 13)), Color::BLACK);
+        assert_eq!(pixmap.get_color((1, 1)), Color::WHITE);
+        assert_eq!(pixmap.get_color((10, 10)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_raster_canvas_has_no_top_raster_handle() {
+        let mut surface = crate::Surface::new_raster_n32_premul((2, 2)).unwrap();
+        assert!(surface.canvas().access_top_raster_handle().is_none());
+    }
+
+    #[test]
+    fn test_is_clip_rect() {
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clip_rect(Rect::new(2.0, 2.0, 10.0, 10.0), None, None);
+        assert!(canvas.is_clip_rect());
+        assert!(!canvas.is_clip_empty());
+
+        canvas.clip_path(&Path::circle((6, 6), 3.0, None), None, true);
+        assert!(!canvas.is_clip_rect());
+        assert!(!canvas.is_clip_empty());
+    }
+
+    #[test]
+    fn test_draw_image_rect_with_cubic_sampling() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((8, 8)).unwrap();
+        image_surface.canvas().clear(Color::RED);
+        let image = image_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.draw_image_rect_with_sampling_options(
+            &image,
+            None,
+            Rect::from_wh(4.0, 4.0),
+            CubicResampler::mitchell(),
+            None,
+        );
+        assert_eq!(canvas.peek_pixels().unwrap().get_color((2, 2)), Color::RED);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_local_to_device_as_3x3_matches_total_matrix() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.translate((10.0, 20.0)).scale((2.0, 3.0));
+
+        let matrix = canvas.local_to_device_as_3x3();
+        assert_eq!(matrix, canvas.total_matrix());
+        assert_eq!(
+            matrix,
+            Matrix::concat(&Matrix::translate((10.0, 20.0)), &Matrix::scale((2.0, 3.0)))
+        );
+    }
+
+    #[test]
+    fn test_total_matrix_copy() {
+        let mut canvas = OwnedCanvas::default();
+        let matrix = canvas.local_to_device_as_3x3();
+        assert!(matrix.is_identity());
+        canvas.rotate(45.0, None);
+        // the copy taken before is not affected by changes to the canvas.
+        assert!(matrix.is_identity());
+        assert!(!canvas.local_to_device_as_3x3().is_identity());
+    }
+
+    #[test]
+    fn test_map_point_to_local_and_rect_to_device() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.translate((10, 20));
+        canvas.scale((2.0, 4.0));
+
+        assert_eq!(
+            canvas.map_point_to_local((14, 28)),
+            Some(Point::new(2.0, 2.0))
+        );
+        assert_eq!(
+            canvas.map_rect_to_device(Rect::from_xywh(1.0, 1.0, 2.0, 2.0)),
+            Rect::from_xywh(12.0, 24.0, 4.0, 8.0)
+        );
+
+        canvas.scale((0.0, 1.0));
+        assert_eq!(canvas.map_point_to_local((14, 28)), None);
+    }
+
+    #[test]
+    fn test_concat_44_round_trip() {
+        let mut canvas = OwnedCanvas::default();
+        let translation = M44::translate(10.0, 20.0, 30.0);
+        canvas.concat_44(&translation);
+        assert_eq!(canvas.local_to_device(), translation);
+    }
+
+    #[test]
+    fn test_set_matrix_replaces_perspective() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.concat_44(&M44::perspective(0.1, 10.0, 1.0));
+        assert_ne!(canvas.local_to_device().row(3), V4::new(0.0, 0.0, 0.0, 1.0));
+
+        canvas.set_matrix(&Matrix::scale((2.0, 2.0)).into());
+        let m = canvas.local_to_device();
+        assert_eq!(m.row(3), V4::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(m, M44::scale(2.0, 2.0, 1.0));
+        assert!(!canvas.local_to_device_as_3x3().has_perspective());
+    }
+
+    #[test]
+    fn test_dimensions_and_color_type() {
+        let mut surface = crate::Surface::new_raster_n32_premul((640, 480)).unwrap();
+        let canvas = surface.canvas();
+        assert_eq!(canvas.width(), 640);
+        assert_eq!(canvas.height(), 480);
+        assert_eq!(canvas.color_type(), ColorType::n32());
+
+        let canvas = Canvas::new((640, 480), None).unwrap();
+        assert_eq!(canvas.width(), 640);
+        assert_eq!(canvas.height(), 480);
+        assert_eq!(canvas.color_type(), ColorType::Unknown);
+    }
+
+    #[test]
+    fn test_reset_matrix_clears_perspective() {
+        let mut canvas = OwnedCanvas::default();
+        let mut perspective = M44::perspective(1.0, 10.0, 1.0);
+        perspective.pre_concat(&M44::translate(1.0, 2.0, 3.0));
+        canvas.concat_44(&perspective);
+        assert_ne!(canvas.local_to_device(), M44::new_identity());
+
+        canvas.reset_matrix();
+        assert_eq!(canvas.local_to_device(), M44::new_identity());
+    }
+
+    #[test]
+    fn test_empty_canvas_creation() {
         let canvas = OwnedCanvas::default();
         drop(canvas)
     }
@@ -2414,6 +3850,409 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_save_layer_with_backdrop_and_init_with_previous() {
+        let backdrop =
+            image_filters::blur((1.0, 1.0), None, None, image_filters::CropRect::default())
+                .unwrap();
+        let rec = SaveLayerRec::default()
+            .backdrop(&backdrop)
+            .flags(SaveLayerFlags::INIT_WITH_PREVIOUS);
+        OwnedCanvas::default().save_layer(&rec);
+    }
+
+    #[test]
+    fn test_auto_canvas_restore_restores_on_drop() {
+        let mut canvas = OwnedCanvas::default();
+        assert_eq!(canvas.save_count(), 1);
+        {
+            let mut guarded = AutoCanvasRestore::guard(&mut canvas, true);
+            assert_eq!(guarded.save_count(), 2);
+            guarded.save();
+            assert_eq!(guarded.save_count(), 3);
+        }
+        assert_eq!(canvas.save_count(), 1);
+    }
+
+    #[test]
+    fn test_save_guard_restores_on_drop() {
+        let mut canvas = OwnedCanvas::default();
+        {
+            let mut guarded = canvas.save_guard();
+            assert_eq!(guarded.save_count(), 2);
+            guarded.translate((10.0, 10.0));
+            {
+                let nested = guarded.save_guard();
+                assert_eq!(nested.save_count(), 3);
+            }
+            assert_eq!(guarded.save_count(), 2);
+        }
+        canvas.assert_layers_balanced(1);
+        assert!(canvas.local_to_device_as_3x3().is_identity());
+    }
+
+    #[test]
+    fn test_save_layer_guard_composites_on_drop() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let mut layer_paint = Paint::default();
+        layer_paint.set_alpha(0x80);
+        {
+            let mut guarded = canvas.save_layer_guard(&SaveLayerRec::default().paint(&layer_paint));
+            assert_eq!(guarded.save_count(), 2);
+            guarded.clear(Color::RED);
+        }
+        canvas.assert_layers_balanced(1);
+
+        let color = canvas.peek_pixels().unwrap().get_color((2, 2));
+        assert_eq!(color.r(), 0xff);
+        assert!((0x7e..=0x80).contains(&color.g()), "{:?}", color);
+        assert_eq!(color.g(), color.b());
+    }
+
+    #[test]
+    fn test_auto_canvas_restore_does_not_restore_twice() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.save();
+        assert_eq!(canvas.save_count(), 2);
+        {
+            let mut guarded = AutoCanvasRestore::guard(&mut canvas, true);
+            assert_eq!(guarded.save_count(), 3);
+            guarded.restore();
+            assert_eq!(guarded.save_count(), 2);
+            guarded.restore();
+            assert_eq!(guarded.save_count(), 2);
+        }
+        assert_eq!(canvas.save_count(), 2);
+    }
+
+    #[test]
+    fn test_assert_layers_balanced() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.save_layer(&SaveLayerRec::default());
+        canvas.assert_layers_balanced(2);
+        canvas.restore();
+        canvas.assert_layers_balanced(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "unbalanced save / restore")]
+    fn test_assert_layers_balanced_panics_on_missing_restore() {
+        let mut canvas = OwnedCanvas::default();
+        canvas.save();
+        canvas.assert_layers_balanced(1);
+    }
+
+    #[test]
+    fn test_clip_path_honors_inverse_fill_type() {
+        let mut surface = crate::Surface::new_raster_n32_premul((30, 30)).unwrap();
+        let canvas = surface.canvas();
+        let mut path = Path::rect(Rect::from_xywh(10.0, 10.0, 10.0, 10.0), None);
+        path.set_fill_type(crate::PathFillType::InverseWinding);
+        canvas.clip_path(&path, None, None);
+        canvas.clear(Color::RED);
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((15, 15)), Color::TRANSPARENT);
+        assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+        assert_eq!(pixmap.get_color((25, 25)), Color::RED);
+    }
+
+    #[test]
+    fn test_read_pixels_alloc() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::RED);
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let (pixels, row_bytes) = canvas.read_pixels_alloc(&info, (0, 0)).unwrap();
+        assert_eq!(row_bytes, 16);
+        assert_eq!(pixels.len(), 64);
+        assert_eq!(&pixels[0..4], &[0xff, 0, 0, 0xff]);
+
+        let mut recorder = PictureRecorder::new();
+        let recording_canvas = recorder.begin_recording(Rect::from_wh(4.0, 4.0), None);
+        assert!(recording_canvas.read_pixels_alloc(&info, (0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_draw_image_irect_blits_without_filtering() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((2, 2)).unwrap();
+        {
+            let canvas = image_surface.canvas();
+            let mut paint = Paint::default();
+            for (i, color) in [Color::RED, Color::GREEN, Color::BLUE, Color::WHITE]
+                .iter()
+                .enumerate()
+            {
+                paint.set_color(*color);
+                let (x, y) = ((i % 2) as f32, (i / 2) as f32);
+                canvas.draw_rect(Rect::from_xywh(x, y, 1.0, 1.0), &paint);
+            }
+        }
+        let image = image_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((8, 8)).unwrap();
+        let canvas = surface.canvas();
+        let linear = SamplingOptions::from(FilterMode::Linear);
+        canvas.draw_image_irect(&image, None, IRect::from_xywh(3, 3, 2, 2), linear, None);
+        canvas.draw_image_irect(
+            &image,
+            Some(&IRect::from_xywh(1, 0, 1, 2)),
+            IRect::from_xywh(0, 0, 1, 2),
+            linear,
+            None,
+        );
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((3, 3)), Color::RED);
+        assert_eq!(pixmap.get_color((4, 3)), Color::GREEN);
+        assert_eq!(pixmap.get_color((3, 4)), Color::BLUE);
+        assert_eq!(pixmap.get_color((4, 4)), Color::WHITE);
+        assert_eq!(pixmap.get_color((0, 0)), Color::GREEN);
+        assert_eq!(pixmap.get_color((0, 1)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_draw_image_rect_strict_and_fast() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((8, 4)).unwrap();
+        {
+            let canvas = image_surface.canvas();
+            let mut paint = Paint::default();
+            paint.set_color(Color::BLUE);
+            canvas.clear(Color::RED);
+            canvas.draw_rect(Rect::from_xywh(4.0, 0.0, 4.0, 4.0), &paint);
+        }
+        let image = image_surface.image_snapshot();
+        let src = Rect::from_wh(4.0, 4.0);
+
+        let mut surface = crate::Surface::new_raster_n32_premul((16, 16)).unwrap();
+        let canvas = surface.canvas();
+        canvas.draw_image_rect_strict(&image, src, Rect::from_wh(16.0, 16.0), None);
+        assert_eq!(canvas.peek_pixels().unwrap().get_color((8, 8)), Color::RED);
+        canvas.draw_image_rect_fast(&image, src, Rect::from_wh(16.0, 16.0), None);
+        assert_eq!(canvas.peek_pixels().unwrap().get_color((8, 8)), Color::RED);
+    }
+
+    #[test]
+    fn test_draw_region_boundary() {
+        let mut region = crate::Region::new();
+        region.set_rects(&[
+            IRect::from_xywh(2, 2, 10, 10),
+            IRect::from_xywh(20, 2, 10, 10),
+        ]);
+        let mut surface = crate::Surface::new_raster_n32_premul((40, 20)).unwrap();
+        let canvas = surface.canvas();
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED).set_stroke_width(2.0);
+        canvas.draw_region_boundary(&region, &paint);
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((2, 6)), Color::RED);
+        assert_eq!(pixmap.get_color((20, 6)), Color::RED);
+        // the insides stay empty.
+        assert_eq!(pixmap.get_color((6, 6)), Color::TRANSPARENT);
+        assert_eq!(pixmap.get_color((25, 6)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_raster_direct_canvas_reused_for_multiple_frames() {
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        let mut canvas = Canvas::from_raster_direct(&info, &mut pixels, None, None).unwrap();
+        for (frame, color) in [Color::RED, Color::BLUE].iter().enumerate() {
+            let save_count = canvas.save();
+            canvas.translate((frame as f32, 0.0));
+            canvas.clip_rect(Rect::from_wh(2.0, 4.0), None, None);
+            canvas.clear(*color);
+            canvas.restore_to_count(save_count);
+
+            canvas.assert_layers_balanced(1);
+            assert!(canvas.local_to_device_as_3x3().is_identity());
+            assert_eq!(
+                canvas.peek_pixels().unwrap().get_color((frame as i32, 0)),
+                *color
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_read_pixels_errors() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::RED);
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixels = vec![0u8; 63];
+        assert_eq!(
+            canvas.try_read_pixels(&info, &mut pixels, 16, (0, 0)),
+            Err(canvas::PixelsError::BufferTooSmall {
+                needed: 64,
+                got: 63
+            })
+        );
+        assert!(!canvas.read_pixels(&info, &mut pixels, 16, (0, 0)));
+
+        let mut pixels = vec![0u8; 64];
+        assert_eq!(
+            canvas.try_read_pixels(&info, &mut pixels, 15, (0, 0)),
+            Err(canvas::PixelsError::RowBytesTooSmall {
+                needed: 16,
+                got: 15
+            })
+        );
+        let unknown = ImageInfo::new((4, 4), ColorType::Unknown, AlphaType::Unknown, None);
+        assert_eq!(
+            canvas.try_read_pixels(&unknown, &mut pixels, 16, (0, 0)),
+            Err(canvas::PixelsError::ConversionUnsupported)
+        );
+        assert_eq!(
+            canvas.try_read_pixels(&info, &mut pixels, 16, (10, 10)),
+            Err(canvas::PixelsError::NativeFailure)
+        );
+        assert_eq!(
+            canvas.try_read_pixels(&info, &mut pixels, 16, (0, 0)),
+            Ok(())
+        );
+        assert_eq!(&pixels[0..4], &[0xff, 0, 0, 0xff]);
+
+        assert_eq!(
+            canvas.try_write_pixels(&info, &pixels[..63], 16, (0, 0)),
+            Err(canvas::PixelsError::BufferTooSmall {
+                needed: 64,
+                got: 63
+            })
+        );
+        assert_eq!(canvas.try_write_pixels(&info, &pixels, 16, (0, 0)), Ok(()));
+
+        let mut without_pixels = Canvas::new((4, 4), None).unwrap();
+        assert_eq!(
+            without_pixels.try_read_pixels(&info, &mut pixels, 16, (0, 0)),
+            Err(canvas::PixelsError::NativeFailure)
+        );
+    }
+
+    #[test]
+    fn test_image_snapshot() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::RED);
+        let image = canvas.image_snapshot().unwrap();
+        // the snapshot is a copy.
+        canvas.clear(Color::BLUE);
+        assert_eq!((4, 4), (image.width(), image.height()));
+        let info = ImageInfo::new((1, 1), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixel = [0u8; 4];
+        assert!(image.read_pixels(
+            &info,
+            &mut pixel,
+            4,
+            (0, 0),
+            crate::image::CachingHint::Disallow
+        ));
+        assert_eq!(pixel, [0xff, 0, 0, 0xff]);
+
+        let mut recorder = PictureRecorder::new();
+        let recording_canvas = recorder.begin_recording(Rect::from_wh(4.0, 4.0), None);
+        assert!(recording_canvas.image_snapshot().is_none());
+        assert!(Canvas::new((4, 4), None)
+            .unwrap()
+            .image_snapshot()
+            .is_none());
+    }
+
+    #[test]
+    fn test_draw_str_align_center_offsets_by_half_the_advance() {
+        use crate::utils::text_utils::Align;
+
+        let mut surface = crate::Surface::new_raster_n32_premul((200, 40)).unwrap();
+        let font = Font::new(Typeface::default(), 20.0);
+        let text = "HHH";
+        let (width, bounds) = font.measure_str(text, None);
+        assert!(width > 0.0);
+
+        surface
+            .canvas()
+            .draw_str_align(text, (100, 30), &font, &Paint::default(), Align::Center);
+
+        let pixmap = surface.peek_pixels().unwrap();
+        let left_edge = (0..200)
+            .find(|&x| (0..40).any(|y| pixmap.get_color((x, y)).a() != 0))
+            .unwrap();
+        let expected = 100.0 - width / 2.0 + bounds.left;
+        assert!(
+            (left_edge as f32 - expected).abs() <= 1.5,
+            "left edge {} expected {}",
+            left_edge,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_base_and_top_props_in_layers() {
+        let props = crate::SurfaceProps::new(Default::default(), PixelGeometry::RGBH);
+        let mut canvas = Canvas::new((4, 4), Some(&props)).unwrap();
+        assert_eq!(canvas.base_props(), props);
+        assert_eq!(canvas.top_props(), props);
+
+        canvas.save_layer(&SaveLayerRec::default());
+        assert_eq!(canvas.base_props(), props);
+        assert_eq!(canvas.top_props().pixel_geometry(), PixelGeometry::Unknown);
+        canvas.restore();
+
+        canvas.save_layer(&SaveLayerRec::default().flags(SaveLayerFlags::PRESERVE_LCD_TEXT));
+        assert_eq!(canvas.top_props(), props);
+        canvas.restore();
+    }
+
+    #[test]
+    fn test_draw_paint_clipped() {
+        let mut surface = crate::Surface::new_raster_n32_premul((10, 10)).unwrap();
+        let canvas = surface.canvas();
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED);
+        paint.set_style(crate::paint::Style::Stroke);
+        canvas.draw_paint_clipped(Rect::from_xywh(2.0, 2.0, 6.0, 6.0), &paint);
+        assert_eq!(canvas.save_count(), 1);
+        assert_eq!(canvas.device_clip_bounds(), Some(IRect::from_wh(10, 10)));
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+        assert_eq!(pixmap.get_color((1, 1)), Color::TRANSPARENT);
+        assert_eq!(pixmap.get_color((8, 8)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_draw_lines() {
+        let mut surface = crate::Surface::new_raster_n32_premul((10, 10)).unwrap();
+        let canvas = surface.canvas();
+        let mut paint = Paint::default();
+        paint.set_color(Color::RED);
+        canvas.draw_lines(
+            &[
+                (Point::new(0.0, 2.5), Point::new(10.0, 2.5)),
+                (Point::new(7.5, 0.0), Point::new(7.5, 10.0)),
+            ],
+            &paint,
+        );
+        canvas.draw_lines(&[], &paint);
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((1, 2)), Color::RED);
+        assert_eq!(pixmap.get_color((7, 8)), Color::RED);
+        // the segments are not connected.
+        assert_eq!(pixmap.get_color((4, 6)), Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_surface_of_canvas() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let owner = unsafe { surface.canvas().surface() }.unwrap();
+        assert!(std::ptr::eq(owner.native(), surface.native()));
+
+        let mut canvas = Canvas::new((4, 4), None).unwrap();
+        assert!(unsafe { canvas.surface() }.is_none());
+    }
+
     #[test]
     fn test_make_surface() {
         let mut pixels: [u32; 4] = Default::default();
@@ -2439,6 +4278,191 @@ mod tests {
         c.clip_rect(Rect::default(), ClipOp::Difference, true);
     }
 
+    #[test]
+    fn test_draw_dots() {
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_dots(
+            &[Point::new(5.0, 5.0), Point::new(15.0, 15.0)],
+            2.0,
+            Color::BLUE,
+        );
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::BLUE);
+        assert_eq!(pixmap.get_color((15, 15)), Color::BLUE);
+        assert_eq!(pixmap.get_color((10, 10)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_draw_text_blob() {
+        let info = ImageInfo::new((64, 32), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut bytes = vec![0u8; info.compute_min_byte_size()];
+        {
+            let mut canvas = Canvas::from_raster_direct(&info, bytes.as_mut(), None, None).unwrap();
+            let font = Font::new(Typeface::default(), 24.0);
+            let blob = TextBlob::from_str("Hi", &font).unwrap();
+            canvas.draw_text_blob(&blob, (4, 24), &Paint::default());
+        }
+        assert!(bytes.chunks(4).any(|pixel| pixel[3] != 0));
+    }
+
+    #[test]
+    fn test_draw_atlas() {
+        let mut atlas_surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        atlas_surface.canvas().clear(Color::RED);
+        let atlas = atlas_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_atlas(
+            &atlas,
+            &[RSXform::new(1.0, 0.0, (8, 8))],
+            &[Rect::from_wh(4.0, 4.0)],
+            None,
+            BlendMode::Modulate,
+            SamplingOptions::default(),
+            None,
+            None,
+        );
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((10, 10)), Color::RED);
+        assert_eq!(pixmap.get_color((2, 2)), Color::WHITE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_draw_atlas_with_mismatched_lengths() {
+        let atlas = crate::Surface::new_raster_n32_premul((4, 4))
+            .unwrap()
+            .image_snapshot();
+        OwnedCanvas::default().draw_atlas(
+            &atlas,
+            &[RSXform::new(1.0, 0.0, (0, 0))],
+            &[],
+            None,
+            BlendMode::Modulate,
+            SamplingOptions::default(),
+            None,
+            None,
+        );
+    }
+
+    #[test]
+    fn test_draw_points_with_empty_slice() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_points(canvas::PointMode::Points, &[], &Paint::default());
+        canvas.draw_polyline(&[], &Paint::default());
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((0, 0)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_draw_polyline() {
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let mut paint = Paint::default();
+        paint.set_stroke_width(2.0);
+        canvas.draw_polyline(
+            &[
+                Point::new(2.0, 5.0),
+                Point::new(18.0, 5.0),
+                Point::new(18.0, 15.0),
+            ],
+            &paint,
+        );
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((10, 5)), Color::BLACK);
+        assert_eq!(pixmap.get_color((18, 10)), Color::BLACK);
+        // not closed
+        assert_eq!(pixmap.get_color((10, 10)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_quick_reject() {
+        let mut canvas = Canvas::new((40, 40), None).unwrap();
+        canvas.clip_rect(Rect::from_wh(10.0, 10.0), None, None);
+
+        assert!(!canvas.quick_reject_rect(Rect::new(5.0, 5.0, 15.0, 15.0)));
+        assert!(canvas.quick_reject_rect(Rect::new(20.0, 20.0, 30.0, 30.0)));
+        assert!(!canvas.quick_reject_irect(IRect::new(5, 5, 15, 15)));
+        assert!(canvas.quick_reject_irect(IRect::new(20, 20, 30, 30)));
+        assert!(canvas.quick_reject_path(&Path::circle((25, 25), 2.0, None)));
+    }
+
+    #[test]
+    fn test_draw_vertices_without_tex_coords_and_colors() {
+        let vertices = Vertices::new(
+            vertices::VertexMode::Triangles,
+            &[
+                Point::new(0.0, 0.0),
+                Point::new(20.0, 0.0),
+                Point::new(0.0, 20.0),
+            ],
+            None,
+            None,
+            None,
+        );
+        assert!(!vertices.has_tex_coords());
+        assert!(!vertices.has_colors());
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_vertices(&vertices, BlendMode::Modulate, &Paint::default());
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((3, 3)), Color::BLACK);
+        assert_eq!(pixmap.get_color((17, 17)), Color::WHITE);
+    }
+
+    #[test]
+    fn test_draw_vertices_triangles_interpolates_colors() {
+        let positions = [
+            Point::new(0.0, 0.0),
+            Point::new(20.0, 0.0),
+            Point::new(20.0, 20.0),
+            Point::new(0.0, 20.0),
+        ];
+        let colors = [Color::RED, Color::RED, Color::BLUE, Color::BLUE];
+        let vertices =
+            Vertices::triangles(&positions, Some(&colors), None, Some(&[0, 1, 2, 0, 2, 3]));
+        assert!(vertices.has_colors());
+        assert_eq!(vertices.index_count(), 6);
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.draw_vertices(&vertices, BlendMode::Dst, &Paint::default());
+        let pixmap = canvas.peek_pixels().unwrap();
+        let top = pixmap.get_color((10, 0));
+        let middle = pixmap.get_color((10, 10));
+        let bottom = pixmap.get_color((10, 19));
+        assert!(top.r() > 0xf0 && top.b() < 0x10);
+        assert!(bottom.b() > 0xf0 && bottom.r() < 0x10);
+        assert!(middle.r() > 0x60 && middle.r() < 0xa0);
+        assert!(middle.b() > 0x60 && middle.b() < 0xa0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vertices_with_invalid_indices() {
+        Vertices::triangle_fan(
+            &[
+                Point::new(0.0, 0.0),
+                Point::new(20.0, 0.0),
+                Point::new(0.0, 20.0),
+            ],
+            None,
+            None,
+            Some(&[0, 1, 3]),
+        );
+    }
+
     /// Regression test for: <https://github.com/rust-skia/rust-skia/issues/427>
     #[test]
     fn test_local_and_device_clip_bounds() {
@@ -2447,4 +4471,473 @@ mod tests {
         let _ = surface.canvas().local_clip_bounds();
         let _ = surface.canvas().local_to_device();
     }
+
+    #[test]
+    fn test_new_with_pixel_geometry_sets_the_props() {
+        let canvas = Canvas::new_with_pixel_geometry((100, 20), PixelGeometry::BGRH).unwrap();
+        let props = canvas.props().unwrap();
+        assert_eq!(PixelGeometry::BGRH, props.pixel_geometry());
+    }
+
+    #[test]
+    fn test_from_pixmap_draws_into_the_pixmaps_pixels() {
+        let mut surface = crate::Surface::new_raster_n32_premul((2, 2)).unwrap();
+        let mut pixmap = surface.peek_pixels().unwrap();
+        unsafe { Canvas::from_pixmap(&mut pixmap) }
+            .unwrap()
+            .clear(Color::WHITE);
+        assert!(pixmap.bytes().unwrap().iter().all(|&b| b == 0xff));
+    }
+
+    #[test]
+    fn test_from_pixmap_without_pixels_fails() {
+        assert!(unsafe { Canvas::from_pixmap(&mut Pixmap::default()) }.is_none());
+    }
+
+    #[test]
+    fn test_draw_annotation_shares_immutable_data() {
+        let value = Data::new_copy(b"https://skia.org");
+        let mut recorder = PictureRecorder::new();
+        let canvas = recorder.begin_recording(Rect::from_wh(100.0, 100.0), None);
+        canvas
+            .draw_annotation(Rect::from_wh(10.0, 10.0), "link", &value)
+            .draw_annotation(Rect::from_xywh(20.0, 20.0, 10.0, 10.0), "link", &value);
+        assert!(recorder.finish_recording_as_picture(None).is_some());
+        assert_eq!(b"https://skia.org", value.as_bytes());
+    }
+
+    #[test]
+    fn test_draw_glyphs_at_places_each_glyph() {
+        let info = ImageInfo::new((64, 32), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let row_bytes = info.min_row_bytes();
+        let mut bytes = vec![0u8; info.compute_min_byte_size()];
+        {
+            let mut canvas = Canvas::from_raster_direct(&info, bytes.as_mut(), None, None).unwrap();
+            let font = Font::new(Typeface::default(), 24.0);
+            let glyphs = font.str_to_glyphs_vec("II");
+            let positions = [Point::new(4.0, 24.0), Point::new(36.0, 24.0)];
+            canvas.draw_glyphs_at(
+                &glyphs,
+                &positions[..],
+                Point::default(),
+                &font,
+                &Paint::default(),
+            );
+        }
+        let inked = |columns: std::ops::Range<usize>| {
+            bytes.chunks(row_bytes).any(|row| {
+                row[columns.start * 4..columns.end * 4]
+                    .chunks(4)
+                    .any(|p| p[3] != 0)
+            })
+        };
+        assert!(inked(0..32));
+        assert!(inked(32..64));
+    }
+
+    #[test]
+    fn test_clip_rect_and_bounds() {
+        let mut canvas = Canvas::new((40, 40), None).unwrap();
+        assert_eq!(
+            Some(IRect::new(10, 10, 30, 30)),
+            canvas.clip_rect_and_bounds(Rect::new(10.0, 10.0, 30.0, 30.0), None, None)
+        );
+        assert_eq!(
+            Some(IRect::new(10, 10, 30, 30)),
+            canvas.device_clip_bounds()
+        );
+        assert_eq!(
+            None,
+            canvas.clip_rect_and_bounds(Rect::new(50.0, 50.0, 60.0, 60.0), None, None)
+        );
+    }
+
+    #[test]
+    fn test_draw_image_with_and_without_paint() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        image_surface.canvas().clear(Color::RED);
+        let image = image_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((8, 8)).unwrap();
+        let canvas = surface.canvas();
+        let sampling = SamplingOptions::new(FilterMode::Linear, crate::MipmapMode::None);
+        canvas
+            .draw_image_with_sampling_options(&image, (0, 0), sampling, None)
+            .draw_image_with_sampling_options(&image, (4, 4), sampling, &Paint::default())
+            .draw_image(&image, (4, 0), None)
+            .draw_image_rect(&image, None, Rect::from_xywh(0.0, 4.0, 4.0, 4.0), None);
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        for p in [(0, 0), (4, 4), (4, 0), (0, 4)].iter() {
+            assert_eq!(Color::RED, pixmap.get_color(*p));
+        }
+    }
+
+    #[test]
+    fn test_local_clip_bounds_raw() {
+        let mut canvas = Canvas::new((40, 40), None).unwrap();
+        canvas.clip_rect(Rect::new(10.0, 10.0, 20.0, 20.0), None, None);
+        assert_eq!(
+            canvas.local_clip_bounds(),
+            Some(canvas.local_clip_bounds_raw())
+        );
+
+        canvas.clip_rect(Rect::new(15.0, 10.0, 15.0, 20.0), None, None);
+        assert_eq!(None, canvas.local_clip_bounds());
+        assert!(canvas.local_clip_bounds_raw().is_empty());
+    }
+
+    #[test]
+    fn test_save_layer_rec_new_with_backdrop() {
+        let mut canvas = Canvas::new((40, 40), None).unwrap();
+        let bounds = Rect::from_wh(20.0, 20.0);
+        let backdrop =
+            image_filters::blur((2.0, 2.0), None, None, image_filters::CropRect::default())
+                .unwrap();
+        let rec = SaveLayerRec::new().bounds(&bounds).backdrop(&backdrop);
+        canvas.save_layer(&rec);
+        assert_eq!(2, canvas.save_count());
+        canvas.restore();
+        assert_eq!(1, canvas.save_count());
+    }
+
+    #[test]
+    fn test_draw_edge_aa_image_set() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        image_surface.canvas().clear(Color::RED);
+        let image = image_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 10)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let set = [
+            canvas::ImageSetEntry::new(
+                &image,
+                Rect::from_xywh(0.0, 0.0, 10.0, 10.0),
+                canvas::QuadAAFlags::ALL,
+            ),
+            canvas::ImageSetEntry::new(
+                &image,
+                Rect::from_xywh(10.0, 0.0, 10.0, 10.0),
+                canvas::QuadAAFlags::NONE,
+            ),
+        ];
+        canvas.draw_edge_aa_image_set(&set, &[], &[], SamplingOptions::default(), None, None);
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::RED, pixmap.get_color((5, 5)));
+        assert_eq!(Color::RED, pixmap.get_color((15, 5)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_draw_edge_aa_image_set_panics_on_missing_clip() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        let image = image_surface.image_snapshot();
+        let mut entry =
+            canvas::ImageSetEntry::new(image, Rect::from_wh(4.0, 4.0), canvas::QuadAAFlags::NONE);
+        entry.has_clip = true;
+        let mut canvas = Canvas::new((4, 4), None).unwrap();
+        canvas.draw_edge_aa_image_set(&[entry], &[], &[], SamplingOptions::default(), None, None);
+    }
+
+    #[test]
+    fn test_draw_edge_aa_quad() {
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 10)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas
+            .draw_edge_aa_quad(
+                Rect::from_xywh(0.0, 0.0, 10.0, 10.0),
+                None,
+                canvas::QuadAAFlags::LEFT | canvas::QuadAAFlags::TOP,
+                Color::BLUE,
+                BlendMode::SrcOver,
+            )
+            .draw_edge_aa_quad(
+                Rect::from_xywh(10.0, 0.0, 10.0, 10.0),
+                Some(&[
+                    Point::new(10.0, 0.0),
+                    Point::new(20.0, 0.0),
+                    Point::new(20.0, 5.0),
+                    Point::new(10.0, 5.0),
+                ]),
+                canvas::QuadAAFlags::NONE,
+                Color::BLUE,
+                BlendMode::SrcOver,
+            );
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::BLUE, pixmap.get_color((5, 5)));
+        assert_eq!(Color::BLUE, pixmap.get_color((15, 2)));
+        assert_eq!(Color::WHITE, pixmap.get_color((15, 8)));
+    }
+
+    #[test]
+    fn test_draw_simple_text_with_glyph_ids_matches_draw_str() {
+        let font = Font::new(Typeface::default(), 24.0);
+        let glyphs = font.str_to_glyphs_vec("H");
+        let glyph_bytes: Vec<u8> = glyphs
+            .iter()
+            .flat_map(|g| g.to_ne_bytes().to_vec())
+            .collect();
+
+        let render = |draw: &dyn Fn(&mut Canvas)| {
+            let mut surface = crate::Surface::new_raster_n32_premul((32, 32)).unwrap();
+            draw(surface.canvas());
+            surface
+                .image_snapshot()
+                .peek_pixels()
+                .map(|pixmap| pixmap.bytes().unwrap().to_vec())
+                .unwrap()
+        };
+
+        let utf8 = render(&|canvas| {
+            canvas.draw_str("H", (4, 24), &font, &Paint::default());
+        });
+        let glyph_ids = render(&|canvas| {
+            canvas.draw_simple_text(
+                &glyph_bytes,
+                crate::TextEncoding::GlyphId,
+                (4, 24),
+                &font,
+                &Paint::default(),
+            );
+        });
+        assert!(utf8.iter().any(|&b| b != 0));
+        assert_eq!(utf8, glyph_ids);
+    }
+
+    #[test]
+    fn test_props_accessors() {
+        let props = crate::SurfaceProps::new(
+            crate::SurfacePropsFlags::USE_DEVICE_INDEPENDENT_FONTS,
+            PixelGeometry::RGBV,
+        );
+        let canvas = Canvas::new((10, 10), Some(&props)).unwrap();
+        let props = canvas.props().unwrap();
+        assert_eq!(PixelGeometry::RGBV, props.pixel_geometry());
+        assert!(props.is_use_device_independent_fonts());
+        assert_eq!(
+            crate::SurfacePropsFlags::USE_DEVICE_INDEPENDENT_FONTS,
+            props.flags()
+        );
+        assert_eq!(
+            Some(PixelGeometry::RGBV),
+            canvas.props().map(|p| p.pixel_geometry())
+        );
+    }
+
+    #[test]
+    fn test_new_with_inline_props() {
+        let canvas = Canvas::new(
+            (10, 10),
+            Some(&crate::SurfaceProps::new(
+                Default::default(),
+                PixelGeometry::RGBH,
+            )),
+        )
+        .unwrap();
+        let props = canvas.props().unwrap();
+        assert_eq!(PixelGeometry::RGBH, props.pixel_geometry());
+        assert!(props.flags().is_empty());
+    }
+
+    #[test]
+    fn test_quick_reject_region_and_device_clip_region() {
+        let mut canvas = Canvas::new((40, 40), None).unwrap();
+        canvas.clip_rect(Rect::new(10.0, 10.0, 20.0, 20.0), None, None);
+        assert!(canvas.quick_reject_region(&crate::Region::from_rect(IRect::new(25, 25, 30, 30))));
+        assert!(!canvas.quick_reject_region(&crate::Region::from_rect(IRect::new(15, 15, 30, 30))));
+        assert_eq!(
+            IRect::new(10, 10, 20, 20),
+            *canvas.device_clip_region().unwrap().bounds()
+        );
+
+        canvas.clip_path(&Path::circle((15, 15), 4.0, None), None, true);
+        assert!(canvas.device_clip_region().is_none());
+
+        canvas.clip_rect(Rect::new(30.0, 30.0, 35.0, 35.0), None, None);
+        assert!(canvas.device_clip_region().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_new_empty_and_from_bitmap() {
+        let canvas = Canvas::new_empty().unwrap();
+        assert_eq!(crate::ISize::new(0, 0), canvas.base_layer_size());
+
+        let mut bitmap = crate::Bitmap::new();
+        assert!(bitmap.set_info(&ImageInfo::new_n32_premul((4, 4), None), None));
+        bitmap.alloc_pixels();
+        let canvas = Canvas::try_from_bitmap(&bitmap, None).unwrap();
+        assert_eq!(crate::ISize::new(4, 4), canvas.base_layer_size());
+        let canvas = Canvas::from_bitmap(&bitmap, None);
+        assert_eq!(crate::ISize::new(4, 4), canvas.base_layer_size());
+    }
+
+    #[test]
+    #[cfg(feature = "gpu")]
+    fn test_flush_and_submit_on_raster_canvas_has_no_effect() {
+        let mut surface = crate::Surface::new_raster_n32_premul((4, 4)).unwrap();
+        surface.canvas().clear(Color::RED).flush_and_submit(true);
+        assert_eq!(
+            Color::RED,
+            surface.canvas().peek_pixels().unwrap().get_color((0, 0))
+        );
+    }
+
+    #[test]
+    fn test_draw_picture_clipped() {
+        let mut recorder = PictureRecorder::new();
+        recorder
+            .begin_recording(Rect::from_wh(20.0, 20.0), None)
+            .draw_color(Color::RED, BlendMode::SrcOver);
+        let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_picture_clipped(&picture, Rect::from_wh(10.0, 20.0), None, None);
+        assert_eq!(1, canvas.save_count());
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::RED, pixmap.get_color((5, 10)));
+        assert_eq!(Color::WHITE, pixmap.get_color((15, 10)));
+    }
+
+    #[test]
+    fn test_clip_kind() {
+        let mut surface = crate::Surface::new_raster_n32_premul((40, 40)).unwrap();
+        let canvas = surface.canvas();
+        assert_eq!(canvas::ClipKind::Rect, canvas.clip_kind());
+        canvas.save();
+        canvas.clip_path(&Path::circle((20, 20), 10.0, None), None, true);
+        assert_eq!(canvas::ClipKind::Complex, canvas.clip_kind());
+        canvas.restore();
+        assert_eq!(canvas::ClipKind::Rect, canvas.clip_kind());
+        canvas.clip_rect(Rect::new(50.0, 50.0, 60.0, 60.0), None, None);
+        assert_eq!(canvas::ClipKind::Empty, canvas.clip_kind());
+    }
+
+    #[test]
+    fn test_clip_kind_of_rrect() {
+        let mut surface = crate::Surface::new_raster_n32_premul((40, 40)).unwrap();
+        let canvas = surface.canvas();
+        canvas.save();
+        canvas.clip_rrect(
+            crate::RRect::new_rect_xy(Rect::new(5.0, 5.0, 35.0, 35.0), 8.0, 8.0),
+            None,
+            false,
+        );
+        assert_eq!(canvas::ClipKind::Complex, canvas.clip_kind());
+        canvas.restore();
+        // without radii, the round rectangle is a plain rectangle.
+        canvas.clip_rrect(
+            crate::RRect::new_rect(Rect::new(5.0, 5.0, 35.0, 35.0)),
+            None,
+            false,
+        );
+        assert_eq!(canvas::ClipKind::Rect, canvas.clip_kind());
+    }
+
+    #[test]
+    fn test_clear_with_color_space() {
+        let linear = crate::ColorSpace::new_srgb_linear();
+        let info = ImageInfo::new(
+            (4, 4),
+            ColorType::RGBAF16,
+            AlphaType::Premul,
+            linear.clone(),
+        );
+        let mut surface = crate::Surface::new_raster(&info, None, None).unwrap();
+        let canvas = surface.canvas();
+        let gray = crate::Color4f::new(0.5, 0.5, 0.5, 1.0);
+
+        canvas.clear_with_color_space(gray, Some(&linear));
+        let in_linear = canvas.peek_pixels().unwrap().get_color((0, 0));
+        assert!((i32::from(in_linear.r()) - 128).abs() <= 1);
+
+        canvas.clear_with_color_space(gray, None);
+        let from_srgb = canvas.peek_pixels().unwrap().get_color((0, 0));
+        assert!(from_srgb.r() < 64);
+    }
+
+    #[test]
+    fn test_draw_image_nine_keeps_corners_unfiltered() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((3, 3)).unwrap();
+        image_surface.canvas().clear(Color::BLUE).draw_rect(
+            Rect::from_wh(1.0, 1.0),
+            &Paint::new(crate::Color4f::from(Color::RED), None),
+        );
+        let image = image_surface.image_snapshot();
+
+        let mut surface = crate::Surface::new_raster_n32_premul((30, 30)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_image_nine(
+            &image,
+            IRect::new(1, 1, 2, 2),
+            Rect::from_wh(30.0, 30.0),
+            FilterMode::Linear,
+            None,
+        );
+
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::RED, pixmap.get_color((0, 0)));
+        assert_eq!(Color::BLUE, pixmap.get_color((15, 15)));
+    }
+
+    #[test]
+    fn test_draw_image_nine_at_half_pixel_offset_rounds_like_the_rounded_dst() {
+        let mut image_surface = crate::Surface::new_raster_n32_premul((3, 3)).unwrap();
+        image_surface.canvas().clear(Color::BLUE).draw_rect(
+            Rect::from_wh(1.0, 1.0),
+            &Paint::new(crate::Color4f::from(Color::RED), None),
+        );
+        let image = image_surface.image_snapshot();
+
+        let draw = |dst: Rect| {
+            let mut surface = crate::Surface::new_raster_n32_premul((16, 16)).unwrap();
+            surface.canvas().draw_image_nine(
+                &image,
+                IRect::new(1, 1, 2, 2),
+                dst,
+                FilterMode::Nearest,
+                None,
+            );
+            let pixmap = surface.peek_pixels().unwrap();
+            pixmap.bytes().unwrap().to_vec()
+        };
+
+        let dst = Rect::new(2.5, 2.5, 12.5, 12.5);
+        let fractional = draw(dst);
+        assert_eq!(fractional, draw(Rect::from(dst.round())));
+
+        // the corner moved half a pixel to the right and down.
+        let pixel = |x: usize, y: usize| {
+            let i = (y * 16 + x) * 4;
+            fractional[i..i + 4].to_vec()
+        };
+        assert_eq!(pixel(2, 2), vec![0, 0, 0, 0]);
+        assert_ne!(pixel(3, 3), vec![0, 0, 0, 0]);
+        assert_ne!(pixel(3, 3), pixel(4, 4));
+    }
+
+    #[test]
+    fn test_draw_rrect_border() {
+        let rrect = crate::RRect::new_rect_xy(Rect::from_wh(20.0, 20.0), 4.0, 4.0);
+        let paint = Paint::new(crate::Color4f::from(Color::RED), None);
+
+        let mut surface = crate::Surface::new_raster_n32_premul((20, 20)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        canvas.draw_rrect_border(rrect, 2.0, &paint);
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::RED, pixmap.get_color((1, 10)));
+        assert_eq!(Color::WHITE, pixmap.get_color((10, 10)));
+
+        canvas.draw_rrect_border(rrect, 15.0, &paint);
+        let pixmap = canvas.peek_pixels().unwrap();
+        assert_eq!(Color::RED, pixmap.get_color((10, 10)));
+    }
 }
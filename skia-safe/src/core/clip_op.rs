@@ -1,2 +1,19 @@
+/// How a clip is combined with the current clip of a [`crate::Canvas`].
+///
+/// Only [`ClipOp::Intersect`] and [`ClipOp::Difference`] are supported, clips can only shrink.
+/// The expanding operations `Union`, `XOR`, `ReverseDifference`, and `Replace` were removed from
+/// Skia. Instead of `Replace`, call [`crate::Canvas::save()`] before clipping and
+/// [`crate::Canvas::restore()`] to get back the previous clip.
 pub use skia_bindings::SkClipOp as ClipOp;
 variant_name!(ClipOp::Difference, clip_op_naming);
+
+#[test]
+fn only_shrinking_clip_ops_are_supported() {
+    // Fails to compile if Skia adds clip ops again.
+    let intersects = |op: ClipOp| match op {
+        ClipOp::Difference => false,
+        ClipOp::Intersect => true,
+    };
+    assert!(intersects(ClipOp::Intersect));
+    assert!(!intersects(ClipOp::Difference));
+}
@@ -2,11 +2,11 @@
 use crate::gpu;
 use crate::{
     prelude::*, AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect,
-    ISize, ImageFilter, ImageGenerator, ImageInfo, Matrix, Paint, Picture, Pixmap, SamplingOptions,
-    Shader, TileMode,
+    ISize, ImageFilter, ImageGenerator, ImageInfo, Matrix, Paint, Picture, Pixmap, RustWStream,
+    SamplingOptions, Shader, TileMode,
 };
 use skia_bindings::{self as sb, SkImage, SkRefCntBase};
-use std::{fmt, mem, ptr};
+use std::{fmt, io, mem, ptr};
 
 pub use super::CubicResampler;
 
@@ -574,6 +574,66 @@ impl Image {
         Data::from_ptr(unsafe { sb::C_SkImage_encodeToData(self.native(), image_format, quality) })
     }
 
+    /// Encodes the image and writes the encoded bytes to `writer` while encoding, without
+    /// buffering the complete encoded image in memory.
+    ///
+    /// Texture backed images are read back into a raster image first.
+    pub fn encode_to_writer(
+        &self,
+        writer: impl io::Write,
+        image_format: EncodedImageFormat,
+        quality: u32,
+    ) -> io::Result<()> {
+        // Keeps the first write error, because `RustWStream` only reports failure to Skia.
+        struct ErrorCapturingWriter<W> {
+            writer: W,
+            error: Option<io::Error>,
+        }
+
+        impl<W: io::Write> io::Write for ErrorCapturingWriter<W> {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.writer.write(buf).map_err(|e| {
+                    let kind = e.kind();
+                    self.error.get_or_insert(e);
+                    kind.into()
+                })
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.writer.flush().map_err(|e| {
+                    let kind = e.kind();
+                    self.error.get_or_insert(e);
+                    kind.into()
+                })
+            }
+        }
+
+        let mut writer = ErrorCapturingWriter {
+            writer,
+            error: None,
+        };
+        let encoded = {
+            let mut stream = RustWStream::new(&mut writer);
+            unsafe {
+                sb::C_SkImage_encodeToStream(
+                    self.native(),
+                    stream.stream_mut(),
+                    image_format,
+                    quality.min(100) as i32,
+                )
+            }
+        };
+
+        match writer.error {
+            Some(error) => Err(error),
+            None if !encoded => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "failed to encode image",
+            )),
+            None => Ok(()),
+        }
+    }
+
     pub fn encoded_data(&self) -> Option<Data> {
         Data::from_ptr(unsafe { sb::C_SkImage_refEncodedData(self.native()) })
     }
@@ -703,6 +763,16 @@ impl Image {
         unsafe { self.native().isLazyGenerated() }
     }
 
+    /// Creates an [`Image`] in target [`ColorSpace`], converting the pixels.
+    /// Returns `None` if [`Image`] could not be created.
+    ///
+    /// Returns original [`Image`] if it is in target [`ColorSpace`].
+    /// Otherwise, converts pixels from [`Image`] [`ColorSpace`] to target [`ColorSpace`].
+    /// If [`Image`] `color_space()` returns `None`, [`Image`] [`ColorSpace`] is assumed to be
+    /// sRGB.
+    ///
+    /// - `color_space` [`ColorSpace`] describing color range of returned [`Image`]
+    /// Returns created [`Image`] in target [`ColorSpace`]
     pub fn new_color_space(&self, color_space: impl Into<Option<ColorSpace>>) -> Option<Image> {
         Image::from_ptr(unsafe {
             sb::C_SkImage_makeColorSpace(
@@ -713,6 +783,8 @@ impl Image {
         })
     }
 
+    /// Like [`Self::new_color_space()`], but for texture-backed images, the conversion is
+    /// performed on the GPU with `direct`.
     #[cfg(feature = "gpu")]
     pub fn new_color_space_with_context<'a>(
         &self,
@@ -728,9 +800,33 @@ impl Image {
         })
     }
 
+    /// Creates a new [`Image`] identical to this one, but with a different [`ColorSpace`].
+    /// This does not convert the underlying pixel data, so the resulting image will draw
+    /// differently.
     pub fn reinterpret_color_space(&self, new_color_space: impl Into<ColorSpace>) -> Option<Image> {
         Image::from_ptr(unsafe {
             sb::C_SkImage_reinterpretColorSpace(self.native(), new_color_space.into().into_ptr())
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Color, EncodedImageFormat, Surface};
+
+    #[test]
+    fn encode_to_writer_writes_the_same_bytes_as_encode_to_data() {
+        let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+
+        let mut encoded = Vec::new();
+        image
+            .encode_to_writer(&mut encoded, EncodedImageFormat::PNG, 100)
+            .unwrap();
+
+        assert_eq!(&encoded[..8], b"\x89PNG\r\n\x1a\n");
+        let data = image.encode_to_data(EncodedImageFormat::PNG).unwrap();
+        assert_eq!(encoded, data.as_bytes());
+    }
+}
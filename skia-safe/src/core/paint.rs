@@ -125,6 +125,16 @@ impl Paint {
         self
     }
 
+    /// Sets the style to [`Style::Stroke`] and the stroke width to `width`.
+    pub fn set_stroke_with_width(&mut self, width: scalar) -> &mut Self {
+        self.set_style(Style::Stroke).set_stroke_width(width)
+    }
+
+    /// Sets the style to [`Style::Fill`].
+    pub fn set_fill(&mut self) -> &mut Self {
+        self.set_style(Style::Fill)
+    }
+
     pub fn color(&self) -> Color {
         self.color4f().to_color()
     }
@@ -390,6 +400,18 @@ fn union_flags() {
     }
 }
 
+#[test]
+fn stroke_and_fill_shortcuts() {
+    let mut paint = Paint::default();
+    paint.set_stroke_with_width(2.0);
+    assert_eq!(paint.style(), Style::Stroke);
+    assert_eq!(paint.stroke_width(), 2.0);
+
+    paint.set_fill();
+    assert_eq!(paint.style(), Style::Fill);
+    assert_eq!(paint.stroke_width(), 2.0);
+}
+
 #[test]
 fn set_color4f_color_space() {
     let mut paint = Paint::default();
@@ -1077,3 +1077,22 @@ fn test_path_rect() {
     let path = Path::rect(r, None);
     assert_eq!(*path.bounds(), r);
 }
+
+#[test]
+fn test_clone_and_eq() {
+    let mut original = Path::new();
+    original.add_rect(Rect::new(0.0, 0.0, 10.0, 10.0), None);
+    let mut cloned = original.clone();
+    assert_eq!(original, cloned);
+
+    cloned.line_to((20.0, 20.0));
+    assert_ne!(original, cloned);
+    assert_eq!(original.count_points(), 4);
+    assert_eq!(*original.bounds(), Rect::new(0.0, 0.0, 10.0, 10.0));
+
+    cloned.toggle_inverse_fill_type();
+    assert_ne!(original, cloned);
+
+    assert!(!original.is_empty());
+    assert!(Path::new().is_empty());
+}
@@ -1,6 +1,11 @@
 use crate::{prelude::*, Canvas, Data, FilterMode, Matrix, Rect, Shader, TileMode};
 use skia_bindings::{self as sb, SkPicture, SkRefCntBase};
-use std::fmt;
+use std::{
+    ffi::c_void,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    process,
+};
 
 pub type Picture = RCHandle<SkPicture>;
 unsafe_send_sync!(Picture);
@@ -35,12 +40,37 @@ impl Picture {
         })
     }
 
-    // TODO: AbortCallback and the function that use it.
-
     pub fn playback(&self, canvas: &mut Canvas) {
         unsafe { sb::C_SkPicture_playback(self.native(), canvas.native_mut()) }
     }
 
+    /// Plays back the picture like [`Self::playback()`], but stops as soon as `should_abort`
+    /// returns `true`, leaving the draw calls played back so far on `canvas`.
+    ///
+    /// `should_abort` is polled before each top-level draw operation. Pictures with only a single
+    /// operation are played back without polling.
+    pub fn playback_with_abort<F: FnMut() -> bool>(
+        &self,
+        canvas: &mut Canvas,
+        mut should_abort: F,
+    ) {
+        unsafe {
+            sb::C_SkPicture_playbackWithAbort(
+                self.native(),
+                canvas.native_mut(),
+                &mut should_abort as *mut F as *mut c_void,
+                Some(abort_trampoline::<F>),
+            )
+        }
+
+        unsafe extern "C" fn abort_trampoline<F: FnMut() -> bool>(
+            should_abort: *mut c_void,
+        ) -> bool {
+            let should_abort = &mut *(should_abort as *mut F);
+            panic::catch_unwind(AssertUnwindSafe(should_abort)).unwrap_or_else(|_| process::abort())
+        }
+    }
+
     pub fn cull_rect(&self) -> Rect {
         Rect::from_native_c(unsafe { sb::C_SkPicture_cullRect(self.native()) })
     }
@@ -107,3 +137,31 @@ impl Picture {
         .unwrap()
     }
 }
+
+#[test]
+fn playback_with_abort_stops_early() {
+    use crate::{utils::CountingCanvas, Paint, PictureRecorder};
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(&Rect::new(0.0, 0.0, 100.0, 100.0), None);
+    for i in 0..10 {
+        canvas.draw_rect(
+            Rect::from_xywh(i as f32 * 10.0, 0.0, 5.0, 5.0),
+            &Paint::default(),
+        );
+    }
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let mut canvas = CountingCanvas::new((100, 100));
+    picture.playback_with_abort(&mut canvas, || false);
+    assert_eq!(10, canvas.counts().rect);
+
+    canvas.reset_counts();
+    let mut polls = 0;
+    picture.playback_with_abort(&mut canvas, || {
+        polls += 1;
+        polls > 3
+    });
+    assert!(polls > 3);
+    assert!(canvas.counts().rect < 10);
+}
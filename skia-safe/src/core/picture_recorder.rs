@@ -102,3 +102,22 @@ fn not_recording_no_canvas() {
     let mut recorder = PictureRecorder::new();
     assert!(recorder.recording_canvas().is_none());
 }
+
+#[test]
+fn record_and_play_back() {
+    use crate::{Color, Paint};
+
+    let mut recorder = PictureRecorder::new();
+    let canvas = recorder.begin_recording(&Rect::new(0.0, 0.0, 10.0, 10.0), None);
+    let mut paint = Paint::default();
+    paint.set_color(Color::RED);
+    canvas.draw_rect(Rect::new(2.0, 2.0, 8.0, 8.0), &paint);
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+
+    let mut surface = crate::Surface::new_raster_n32_premul((10, 10)).unwrap();
+    let target = surface.canvas();
+    target.draw_picture(&picture, None, None);
+    let pixmap = target.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((5, 5)), Color::RED);
+    assert_eq!(pixmap.get_color((0, 0)), Color::TRANSPARENT);
+}
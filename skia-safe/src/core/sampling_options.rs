@@ -55,6 +55,20 @@ pub struct FilterOptions {
     pub mipmap: MipmapMode,
 }
 
+/// The filter quality that was previously set on a [`crate::Paint`]. Helps migrating to
+/// [`SamplingOptions`], see [`SamplingOptions::from_filter_quality()`].
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FilterQuality {
+    /// Nearest neighbor sampling.
+    None,
+    /// Bilinear sampling.
+    Low,
+    /// Bilinear sampling with the nearest mipmap level.
+    Medium,
+    /// Bicubic sampling with the Mitchell filter.
+    High,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Debug)]
 #[allow(deprecated)]
@@ -87,6 +101,42 @@ impl SamplingOptions {
             ..Default::default()
         }
     }
+
+    /// Samples the nearest pixel, without mipmaps.
+    pub fn nearest() -> Self {
+        FilterMode::Nearest.into()
+    }
+
+    /// Interpolates linearly between the nearest pixels, without mipmaps.
+    pub fn linear() -> Self {
+        FilterMode::Linear.into()
+    }
+
+    /// Bicubic sampling with the "Mitchell" filter, see [`CubicResampler::mitchell()`].
+    pub fn mitchell() -> Self {
+        CubicResampler::mitchell().into()
+    }
+
+    /// Bicubic sampling with the "Catmull-Rom" filter, see [`CubicResampler::catmull_rom()`].
+    pub fn catmull_rom() -> Self {
+        CubicResampler::catmull_rom().into()
+    }
+
+    /// The sampling options that match the legacy filter quality, as Skia used to map them.
+    pub fn from_filter_quality(filter_quality: FilterQuality) -> Self {
+        match filter_quality {
+            FilterQuality::None => Self::nearest(),
+            FilterQuality::Low => Self::linear(),
+            FilterQuality::Medium => Self::new(FilterMode::Linear, MipmapMode::Nearest),
+            FilterQuality::High => Self::mitchell(),
+        }
+    }
+}
+
+impl From<FilterQuality> for SamplingOptions {
+    fn from(filter_quality: FilterQuality) -> Self {
+        Self::from_filter_quality(filter_quality)
+    }
 }
 
 impl From<FilterMode> for SamplingOptions {
@@ -122,3 +172,60 @@ impl From<CubicResampler> for SamplingOptions {
         }
     }
 }
+
+#[test]
+fn test_sampling_options_presets_differ_when_downscaling() {
+    use crate::{Color, Paint, Rect, Surface};
+
+    let mut image_surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    {
+        let canvas = image_surface.canvas();
+        let mut paint = Paint::default();
+        for y in 0..16 {
+            for x in 0..16 {
+                let v = ((x * 37 + y * 91) % 256) as u8;
+                paint.set_color(Color::from_rgb(v, 255 - v, (v / 2) ^ 0x55));
+                canvas.draw_rect(Rect::from_xywh(x as f32, y as f32, 1.0, 1.0), &paint);
+            }
+        }
+    }
+    let image = image_surface.image_snapshot();
+
+    let downscaled = |sampling: SamplingOptions| {
+        let mut surface = Surface::new_raster_n32_premul((5, 5)).unwrap();
+        surface.canvas().draw_image_rect_with_sampling_options(
+            &image,
+            None,
+            Rect::from_wh(5.0, 5.0),
+            sampling,
+            None,
+        );
+        let pixmap = surface.peek_pixels().unwrap();
+        pixmap.bytes().unwrap().to_vec()
+    };
+
+    let presets = [
+        ("nearest", SamplingOptions::nearest()),
+        ("linear", SamplingOptions::linear()),
+        ("mitchell", SamplingOptions::mitchell()),
+        ("catmull_rom", SamplingOptions::catmull_rom()),
+    ];
+    let results: Vec<_> = presets
+        .iter()
+        .map(|(name, sampling)| (name, downscaled(*sampling)))
+        .collect();
+    for (i, (name_a, a)) in results.iter().enumerate() {
+        for (name_b, b) in &results[i + 1..] {
+            assert_ne!(a, b, "{} and {} should differ", name_a, name_b);
+        }
+    }
+
+    assert_eq!(
+        SamplingOptions::from_filter_quality(FilterQuality::None),
+        SamplingOptions::nearest()
+    );
+    assert_eq!(
+        SamplingOptions::from_filter_quality(FilterQuality::High),
+        SamplingOptions::mitchell()
+    );
+}
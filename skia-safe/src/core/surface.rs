@@ -136,6 +136,9 @@ impl Surface {
         })
     }
 
+    /// Returns a [`Surface`] on a new render target that is allocated by `context`.
+    ///
+    /// The [`Canvas`] of the surface draws with `context`, see [`Canvas::recording_context()`].
     pub fn new_render_target(
         context: &mut gpu::RecordingContext,
         budgeted: crate::Budgeted,
@@ -351,6 +354,14 @@ impl Surface {
         Canvas::borrow_from_native_mut(canvas_ref)
     }
 
+    /// Calls `f` with the [`Canvas`] that draws into this [`Surface`].
+    ///
+    /// The [`Canvas`] reference can not escape the closure, so it can not be held across
+    /// subsequent mutations of the [`Surface`], for example [`Self::image_snapshot()`].
+    pub fn with_canvas<R>(&mut self, f: impl FnOnce(&mut Canvas) -> R) -> R {
+        f(self.canvas())
+    }
+
     // TODO: why is self mutable here?
     pub fn new_surface(&mut self, info: &ImageInfo) -> Option<Surface> {
         Surface::from_ptr(unsafe { sb::C_SkSurface_makeSurface(self.native_mut(), info.native()) })
@@ -580,3 +591,49 @@ fn test_drawing_owned_as_exclusive_ref_ergonomics() {
         surface.draw(canvas, (10.0, 10.0), SamplingOptions::default(), None);
     }
 }
+
+#[test]
+fn draw_into_a_surface_created_by_a_canvas() {
+    let mut surface = Surface::new_raster_n32_premul((16, 16)).unwrap();
+    let mut sub_surface = surface
+        .canvas()
+        .new_surface(&ImageInfo::new_n32_premul((8, 8), None), None)
+        .unwrap();
+    sub_surface.with_canvas(|canvas| {
+        canvas.clear(crate::Color::RED);
+    });
+    let image = sub_surface.image_snapshot();
+    assert_eq!(ISize::new(8, 8), image.dimensions());
+    surface.with_canvas(|canvas| {
+        canvas.draw_image(&image, (0, 0), None);
+    });
+}
+
+#[cfg(all(
+    feature = "gl-headless",
+    any(target_os = "linux", target_os = "android")
+))]
+#[test]
+fn render_target_canvas_draws_with_the_gpu_context() {
+    // EGL may not be available, for example on CI machines without GPU drivers.
+    let mut context = match gpu::gl::HeadlessContext::new(None) {
+        Some(context) => context,
+        None => return,
+    };
+    let mut surface = Surface::new_render_target(
+        context.direct_context(),
+        crate::Budgeted::Yes,
+        &ImageInfo::new_n32_premul((16, 16), None),
+        None,
+        None,
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(surface.canvas().recording_context().is_some());
+    assert!(Surface::new_raster_n32_premul((16, 16))
+        .unwrap()
+        .canvas()
+        .recording_context()
+        .is_none());
+}
@@ -92,6 +92,8 @@ impl fmt::Debug for SurfaceProps {
 impl SurfaceProps {
     // TODO: do we need to wrap the constructor(s) with InitType?
 
+    /// Creates [`SurfaceProps`] inline, for example to pass them to [`crate::Canvas::new()`]:
+    /// `Canvas::new(size, Some(&SurfaceProps::new(Default::default(), PixelGeometry::RGBH)))`.
     pub fn new(flags: SurfacePropsFlags, pixel_geometry: PixelGeometry) -> SurfaceProps {
         Self::from_native_c(unsafe {
             SkSurfaceProps::new1(flags.bits(), pixel_geometry.into_native())
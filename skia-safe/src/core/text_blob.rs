@@ -407,3 +407,39 @@ fn test_point_size_equals_size_of_two_scalars_used_in_alloc_run_pos() {
     use std::mem;
     assert_eq!(mem::size_of::<Point>(), mem::size_of::<[scalar; 2]>())
 }
+
+#[test]
+fn test_rsxform_run_rotated_for_vertical_text() {
+    let font = Font::new(Typeface::default(), 10.0);
+    let glyphs = font.str_to_glyphs_vec("AB");
+
+    let blob_with_xforms = |xforms: [RSXform; 2]| {
+        let mut builder = TextBlobBuilder::new();
+        let (run_glyphs, run_xforms) = builder.alloc_run_rsxform(&font, 2);
+        run_glyphs.copy_from_slice(&glyphs);
+        run_xforms.copy_from_slice(&xforms);
+        builder.make().unwrap()
+    };
+
+    // each glyph rotated by 90 degrees and stacked vertically.
+    let vertical = blob_with_xforms([
+        RSXform::new(0.0, 1.0, (0.0, 0.0)),
+        RSXform::new(0.0, 1.0, (0.0, 200.0)),
+    ]);
+    let bounds = vertical.bounds();
+    assert!(bounds.height() > 200.0);
+    assert!(bounds.height() > bounds.width());
+
+    let horizontal = blob_with_xforms([
+        RSXform::new(1.0, 0.0, (0.0, 0.0)),
+        RSXform::new(1.0, 0.0, (200.0, 0.0)),
+    ]);
+    let bounds = horizontal.bounds();
+    assert!(bounds.width() > 200.0);
+    assert!(bounds.width() > bounds.height());
+
+    let mut surface = crate::Surface::new_raster_n32_premul((50, 300)).unwrap();
+    surface
+        .canvas()
+        .draw_text_blob(&vertical, (25, 25), &Paint::default());
+}
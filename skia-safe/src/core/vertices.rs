@@ -46,16 +46,26 @@ impl fmt::Debug for Vertices {
 }
 
 impl Vertices {
-    pub fn new_copy(
+    /// Creates [`Vertices`] by copying the `positions` and the optional per vertex `tex_coords`
+    /// and `colors`, which must have the same length as `positions` if present. `indices` must
+    /// only refer to existing vertices.
+    pub fn new(
         mode: VertexMode,
         positions: &[Point],
-        texs: &[Point],
-        colors: &[Color],
+        tex_coords: Option<&[Point]>,
+        colors: Option<&[Color]>,
         indices: Option<&[u16]>,
     ) -> Vertices {
         let vertex_count = positions.len();
-        assert_eq!(texs.len(), vertex_count);
-        assert_eq!(colors.len(), vertex_count);
+        if let Some(tex_coords) = tex_coords {
+            assert_eq!(tex_coords.len(), vertex_count);
+        }
+        if let Some(colors) = colors {
+            assert_eq!(colors.len(), vertex_count);
+        }
+        if let Some(indices) = indices {
+            assert!(indices.iter().all(|&i| usize::from(i) < vertex_count));
+        }
 
         let indices_ptr = indices.map(|i| i.as_ptr()).unwrap_or(ptr::null());
         let indices_count = indices.map(|i| i.len()).unwrap_or(0);
@@ -63,10 +73,12 @@ impl Vertices {
         Vertices::from_ptr(unsafe {
             sb::C_SkVertices_MakeCopy(
                 mode,
-                vertex_count as _,
+                vertex_count.try_into().unwrap(),
                 positions.native().as_ptr(),
-                texs.native().as_ptr(),
-                colors.native().as_ptr(),
+                tex_coords
+                    .map(|t| t.native().as_ptr())
+                    .unwrap_or(ptr::null()),
+                colors.map(|c| c.native().as_ptr()).unwrap_or(ptr::null()),
                 indices_count.try_into().unwrap(),
                 indices_ptr,
             )
@@ -74,6 +86,64 @@ impl Vertices {
         .unwrap()
     }
 
+    /// Creates [`VertexMode::Triangles`] [`Vertices`], see [`Self::new()`].
+    pub fn triangles(
+        positions: &[Point],
+        colors: Option<&[Color]>,
+        tex_coords: Option<&[Point]>,
+        indices: Option<&[u16]>,
+    ) -> Vertices {
+        Self::new(
+            VertexMode::Triangles,
+            positions,
+            tex_coords,
+            colors,
+            indices,
+        )
+    }
+
+    /// Creates [`VertexMode::TriangleStrip`] [`Vertices`], see [`Self::new()`].
+    pub fn triangle_strip(
+        positions: &[Point],
+        colors: Option<&[Color]>,
+        tex_coords: Option<&[Point]>,
+        indices: Option<&[u16]>,
+    ) -> Vertices {
+        Self::new(
+            VertexMode::TriangleStrip,
+            positions,
+            tex_coords,
+            colors,
+            indices,
+        )
+    }
+
+    /// Creates [`VertexMode::TriangleFan`] [`Vertices`], see [`Self::new()`].
+    pub fn triangle_fan(
+        positions: &[Point],
+        colors: Option<&[Color]>,
+        tex_coords: Option<&[Point]>,
+        indices: Option<&[u16]>,
+    ) -> Vertices {
+        Self::new(
+            VertexMode::TriangleFan,
+            positions,
+            tex_coords,
+            colors,
+            indices,
+        )
+    }
+
+    pub fn new_copy(
+        mode: VertexMode,
+        positions: &[Point],
+        texs: &[Point],
+        colors: &[Color],
+        indices: Option<&[u16]>,
+    ) -> Vertices {
+        Self::new(mode, positions, Some(texs), Some(colors), indices)
+    }
+
     pub const MAX_CUSTOM_ATTRIBUTES: usize = 8;
 
     pub fn unique_id(&self) -> u32 {
@@ -145,6 +145,11 @@ impl DirectContext {
         self
     }
 
+    /// Abandons all GPU resources and assumes the underlying backend 3D API context is no longer
+    /// usable. Call this if you have lost the associated GPU context, and thus internal texture,
+    /// buffer, etc. references/IDs are now invalid. After this call, all subsequent draws to
+    /// surfaces of this context are silently dropped, and [`RecordingContext::abandoned()`]
+    /// returns `true`.
     pub fn abandon(&mut self) -> &mut Self {
         unsafe {
             // self.native_mut().abandonContext()
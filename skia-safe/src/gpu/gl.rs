@@ -1,6 +1,17 @@
 mod extensions;
 pub use extensions::*;
 
+#[cfg(all(
+    feature = "gl-headless",
+    any(target_os = "linux", target_os = "android")
+))]
+mod headless;
+#[cfg(all(
+    feature = "gl-headless",
+    any(target_os = "linux", target_os = "android")
+))]
+pub use headless::*;
+
 mod interface;
 pub use interface::*;
 
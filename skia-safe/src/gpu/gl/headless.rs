@@ -0,0 +1,241 @@
+//! An offscreen OpenGL context for rendering on machines without a display.
+//!
+//! The context is set up with EGL and a 1x1 pbuffer surface. Rendering is expected to happen into
+//! GPU backed [`crate::Surface`]s that are created with [`HeadlessContext::direct_context()`].
+//!
+//! Platform requirements:
+//! - Linux or Android with an EGL implementation (`libEGL`) that supports pbuffer surfaces. On
+//!   other platforms, the module is not available.
+//! - On Mesa based systems without a display server, set `EGL_PLATFORM=surfaceless` so that the
+//!   default EGL display does not try to connect to X11 or Wayland.
+
+use crate::{
+    gpu::{gl::Interface, ContextOptions, DirectContext},
+    prelude::*,
+};
+use std::{ffi::c_void, fmt, ptr};
+
+type EGLDisplay = *mut c_void;
+type EGLConfig = *mut c_void;
+type EGLContext = *mut c_void;
+type EGLSurface = *mut c_void;
+type EGLBoolean = u32;
+type EGLenum = u32;
+type EGLint = i32;
+
+const EGL_TRUE: EGLBoolean = 1;
+const EGL_NONE: EGLint = 0x3038;
+const EGL_ALPHA_SIZE: EGLint = 0x3021;
+const EGL_BLUE_SIZE: EGLint = 0x3022;
+const EGL_GREEN_SIZE: EGLint = 0x3023;
+const EGL_RED_SIZE: EGLint = 0x3024;
+const EGL_STENCIL_SIZE: EGLint = 0x3026;
+const EGL_SURFACE_TYPE: EGLint = 0x3033;
+const EGL_RENDERABLE_TYPE: EGLint = 0x3040;
+const EGL_HEIGHT: EGLint = 0x3056;
+const EGL_WIDTH: EGLint = 0x3057;
+const EGL_CONTEXT_CLIENT_VERSION: EGLint = 0x3098;
+const EGL_PBUFFER_BIT: EGLint = 0x0001;
+const EGL_OPENGL_ES2_BIT: EGLint = 0x0004;
+const EGL_OPENGL_BIT: EGLint = 0x0008;
+const EGL_OPENGL_ES_API: EGLenum = 0x30A0;
+const EGL_OPENGL_API: EGLenum = 0x30A2;
+
+#[link(name = "EGL")]
+extern "C" {
+    fn eglGetDisplay(display_id: *mut c_void) -> EGLDisplay;
+    fn eglInitialize(dpy: EGLDisplay, major: *mut EGLint, minor: *mut EGLint) -> EGLBoolean;
+    fn eglTerminate(dpy: EGLDisplay) -> EGLBoolean;
+    fn eglBindAPI(api: EGLenum) -> EGLBoolean;
+    fn eglChooseConfig(
+        dpy: EGLDisplay,
+        attrib_list: *const EGLint,
+        configs: *mut EGLConfig,
+        config_size: EGLint,
+        num_config: *mut EGLint,
+    ) -> EGLBoolean;
+    fn eglCreatePbufferSurface(
+        dpy: EGLDisplay,
+        config: EGLConfig,
+        attrib_list: *const EGLint,
+    ) -> EGLSurface;
+    fn eglDestroySurface(dpy: EGLDisplay, surface: EGLSurface) -> EGLBoolean;
+    fn eglCreateContext(
+        dpy: EGLDisplay,
+        config: EGLConfig,
+        share_context: EGLContext,
+        attrib_list: *const EGLint,
+    ) -> EGLContext;
+    fn eglDestroyContext(dpy: EGLDisplay, ctx: EGLContext) -> EGLBoolean;
+    fn eglMakeCurrent(
+        dpy: EGLDisplay,
+        draw: EGLSurface,
+        read: EGLSurface,
+        ctx: EGLContext,
+    ) -> EGLBoolean;
+}
+
+/// An EGL pbuffer backed OpenGL context and the [`DirectContext`] that renders with it.
+///
+/// The EGL context is made current on the thread that creates the [`HeadlessContext`], and all
+/// rendering must happen on that thread.
+pub struct HeadlessContext {
+    // Must be dropped before the EGL context is destroyed.
+    direct_context: Option<DirectContext>,
+    display: EGLDisplay,
+    surface: EGLSurface,
+    context: EGLContext,
+}
+
+impl fmt::Debug for HeadlessContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeadlessContext")
+            .field("direct_context", &self.direct_context)
+            .finish()
+    }
+}
+
+impl Drop for HeadlessContext {
+    fn drop(&mut self) {
+        drop(self.direct_context.take());
+        unsafe {
+            eglMakeCurrent(
+                self.display,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            eglDestroyContext(self.display, self.context);
+            eglDestroySurface(self.display, self.surface);
+            eglTerminate(self.display);
+        }
+    }
+}
+
+impl HeadlessContext {
+    /// Creates an offscreen OpenGL context on the default EGL display, makes it current, and
+    /// creates a [`DirectContext`] for it.
+    ///
+    /// Desktop OpenGL is tried first, and if that is not available, OpenGL ES 2.
+    ///
+    /// Returns `None` if no EGL display is available or the context could not be created.
+    pub fn new<'a>(options: impl Into<Option<&'a ContextOptions>>) -> Option<Self> {
+        let display = unsafe { eglGetDisplay(ptr::null_mut()) };
+        if display.is_null() {
+            return None;
+        }
+        if unsafe { eglInitialize(display, ptr::null_mut(), ptr::null_mut()) } != EGL_TRUE {
+            return None;
+        }
+
+        let (config, renderable_type) = match [
+            (EGL_OPENGL_API, EGL_OPENGL_BIT),
+            (EGL_OPENGL_ES_API, EGL_OPENGL_ES2_BIT),
+        ]
+        .iter()
+        .find_map(|(api, renderable_type)| {
+            let config = choose_config(display, *renderable_type)?;
+            // The API is bound per thread and used by `eglCreateContext()` below.
+            (unsafe { eglBindAPI(*api) } == EGL_TRUE).if_true_some((config, *renderable_type))
+        }) {
+            Some(found) => found,
+            None => {
+                unsafe { eglTerminate(display) };
+                return None;
+            }
+        };
+
+        let surface_attributes = [EGL_WIDTH, 1, EGL_HEIGHT, 1, EGL_NONE];
+        let surface =
+            unsafe { eglCreatePbufferSurface(display, config, surface_attributes.as_ptr()) };
+        if surface.is_null() {
+            unsafe { eglTerminate(display) };
+            return None;
+        }
+
+        let context = unsafe {
+            if renderable_type == EGL_OPENGL_ES2_BIT {
+                let context_attributes = [EGL_CONTEXT_CLIENT_VERSION, 2, EGL_NONE];
+                eglCreateContext(
+                    display,
+                    config,
+                    ptr::null_mut(),
+                    context_attributes.as_ptr(),
+                )
+            } else {
+                eglCreateContext(display, config, ptr::null_mut(), [EGL_NONE].as_ptr())
+            }
+        };
+
+        let mut headless = HeadlessContext {
+            direct_context: None,
+            display,
+            surface,
+            context,
+        };
+
+        if context.is_null()
+            || unsafe { eglMakeCurrent(display, surface, surface, context) } != EGL_TRUE
+        {
+            return None;
+        }
+
+        headless.direct_context = DirectContext::new_gl(Interface::new_native(), options);
+        headless.direct_context.is_some().if_true_some(headless)
+    }
+
+    /// The [`DirectContext`] to create GPU backed [`crate::Surface`]s with.
+    pub fn direct_context(&mut self) -> &mut DirectContext {
+        self.direct_context.as_mut().unwrap()
+    }
+
+    /// Makes the EGL context current on the calling thread again, for example after another
+    /// OpenGL context was used in between.
+    pub fn make_current(&mut self) -> bool {
+        unsafe {
+            eglMakeCurrent(self.display, self.surface, self.surface, self.context) == EGL_TRUE
+        }
+    }
+}
+
+impl DirectContext {
+    /// Creates a [`HeadlessContext`], an offscreen OpenGL context that does not need a window or
+    /// display server. See [`HeadlessContext::new()`].
+    pub fn new_gl_headless<'a>(
+        options: impl Into<Option<&'a ContextOptions>>,
+    ) -> Option<HeadlessContext> {
+        HeadlessContext::new(options)
+    }
+}
+
+fn choose_config(display: EGLDisplay, renderable_type: EGLint) -> Option<EGLConfig> {
+    let attributes = [
+        EGL_SURFACE_TYPE,
+        EGL_PBUFFER_BIT,
+        EGL_RENDERABLE_TYPE,
+        renderable_type,
+        EGL_RED_SIZE,
+        8,
+        EGL_GREEN_SIZE,
+        8,
+        EGL_BLUE_SIZE,
+        8,
+        EGL_ALPHA_SIZE,
+        8,
+        EGL_STENCIL_SIZE,
+        8,
+        EGL_NONE,
+    ];
+    let mut config: EGLConfig = ptr::null_mut();
+    let mut num_configs: EGLint = 0;
+    let chosen = unsafe {
+        eglChooseConfig(
+            display,
+            attributes.as_ptr(),
+            &mut config,
+            1,
+            &mut num_configs,
+        )
+    };
+    (chosen == EGL_TRUE && num_configs > 0).if_true_some(config)
+}
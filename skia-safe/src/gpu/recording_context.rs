@@ -74,6 +74,9 @@ impl RecordingContext {
 
     // TODO: GrContext_Base::threadSafeProxy
 
+    /// Returns `true` if the context was abandoned or if the backend specific context has gotten
+    /// into an unrecoverable, lost state (e.g. in Vulkan backend if we've gotten a
+    /// `VK_ERROR_DEVICE_LOST`).
     pub fn abandoned(&mut self) -> bool {
         unsafe { sb::C_GrRecordingContext_abandoned(self.native_mut()) }
     }
@@ -7,8 +7,13 @@ use crate::prelude::*;
 use crate::Data;
 use skia_bindings as sb;
 use skia_bindings::{SkDynamicMemoryWStream, SkMemoryStream, SkStream, SkStreamAsset, SkWStream};
-use std::ptr;
-use std::{fmt, marker::PhantomData};
+use std::{
+    ffi::c_void,
+    fmt, io,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    process, ptr, slice,
+};
 
 /// Trait representing an Skia allocated Stream type with a base class of SkStream.
 #[repr(transparent)]
@@ -157,6 +162,75 @@ impl DynamicMemoryWStream {
     }
 }
 
+/// A write stream that forwards everything Skia writes to a Rust [`io::Write`].
+///
+/// Write errors are reported to Skia as failed writes. The error itself is not preserved, wrap the
+/// writer if it's needed.
+pub struct RustWStream<'a> {
+    inner: Handle<sb::RustWStream>,
+    _pd: PhantomData<&'a mut ()>,
+}
+
+impl NativeBase<SkWStream> for sb::RustWStream {}
+
+impl NativeDrop for sb::RustWStream {
+    fn drop(&mut self) {
+        unsafe {
+            sb::C_RustWStream_destruct(self);
+        }
+    }
+}
+
+impl fmt::Debug for RustWStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RustWStream").finish()
+    }
+}
+
+impl<'a> RustWStream<'a> {
+    pub fn new<W: io::Write>(writer: &'a mut W) -> Self {
+        // Panics must not unwind into Skia.
+
+        unsafe extern "C" fn write_trampoline<W: io::Write>(
+            writer: *mut c_void,
+            buffer: *const c_void,
+            size: usize,
+        ) -> bool {
+            if size == 0 {
+                return true;
+            }
+            let writer = &mut *(writer as *mut W);
+            let buffer = slice::from_raw_parts(buffer as *const u8, size);
+            panic::catch_unwind(AssertUnwindSafe(|| writer.write_all(buffer).is_ok()))
+                .unwrap_or_else(|_| process::abort())
+        }
+
+        unsafe extern "C" fn flush_trampoline<W: io::Write>(writer: *mut c_void) {
+            let writer = &mut *(writer as *mut W);
+            panic::catch_unwind(AssertUnwindSafe(|| {
+                let _ = writer.flush();
+            }))
+            .unwrap_or_else(|_| process::abort())
+        }
+
+        RustWStream {
+            inner: Handle::construct(|w_stream| unsafe {
+                sb::C_RustWStream_construct(
+                    w_stream,
+                    writer as *mut W as *mut c_void,
+                    Some(write_trampoline::<W>),
+                    Some(flush_trampoline::<W>),
+                )
+            }),
+            _pd: PhantomData,
+        }
+    }
+
+    pub(crate) fn stream_mut(&mut self) -> &mut SkWStream {
+        self.inner.native_mut().base_mut()
+    }
+}
+
 #[test]
 fn detaching_empty_dynamic_memory_w_stream_leads_to_non_null_data() {
     let mut stream = DynamicMemoryWStream::new();
@@ -169,3 +243,15 @@ fn memory_stream_from_bytes() {
     let stream = MemoryStream::from_bytes(&[1, 2, 3]);
     drop(stream);
 }
+
+#[test]
+fn rust_w_stream_forwards_writes() {
+    let mut bytes = Vec::new();
+    {
+        let mut stream = RustWStream::new(&mut bytes);
+        assert!(unsafe {
+            sb::C_SkWStream_write(stream.stream_mut(), [1u8, 2, 3].as_ptr() as _, 3)
+        });
+    }
+    assert_eq!(bytes, [1, 2, 3]);
+}
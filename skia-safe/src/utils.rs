@@ -1,12 +1,18 @@
 mod camera;
+mod counting_canvas;
 mod custom_typeface;
+mod debug_recorder;
 mod null_canvas;
+mod nway_canvas;
 mod ordered_font_mgr;
 pub mod parse_path;
 pub mod shadow_utils;
 pub mod text_utils;
 
 pub use camera::*;
+pub use counting_canvas::*;
 pub use custom_typeface::*;
+pub use debug_recorder::*;
 pub use null_canvas::*;
+pub use nway_canvas::*;
 pub use ordered_font_mgr::*;
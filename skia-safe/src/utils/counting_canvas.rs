@@ -0,0 +1,209 @@
+use crate::{prelude::*, utils::NWayCanvas, ISize};
+use skia_bindings::{self as sb, CountingCanvas_DrawKind as DrawKind};
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// The number of draw calls a [`CountingCanvas`] has seen, per kind of primitive.
+///
+/// Convenience calls are counted by the primitive they are implemented with, for example
+/// [`crate::Canvas::draw_circle()`] as an oval, [`crate::Canvas::draw_line()`] as points, and
+/// [`crate::Canvas::clear()`] as a paint.
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct DrawCounts {
+    pub paint: usize,
+    pub behind: usize,
+    pub points: usize,
+    pub rect: usize,
+    pub region: usize,
+    pub oval: usize,
+    pub arc: usize,
+    pub rrect: usize,
+    pub drrect: usize,
+    pub path: usize,
+    pub vertices: usize,
+    pub patch: usize,
+    pub image: usize,
+    pub image_rect: usize,
+    pub image_lattice: usize,
+    pub atlas: usize,
+    pub edge_aa_image_set: usize,
+    pub text_blob: usize,
+    /// Text and glyphs that are not drawn with a [`crate::TextBlob`].
+    pub glyphs: usize,
+    pub annotation: usize,
+    pub shadow: usize,
+    pub drawable: usize,
+    /// Pictures that are drawn as a whole. Small pictures are played back instead, and their
+    /// draw calls are counted individually.
+    pub picture: usize,
+    pub edge_aa_quad: usize,
+}
+
+impl DrawCounts {
+    /// The number of all draw calls.
+    pub fn total(&self) -> usize {
+        self.paint
+            + self.behind
+            + self.points
+            + self.rect
+            + self.region
+            + self.oval
+            + self.arc
+            + self.rrect
+            + self.drrect
+            + self.path
+            + self.vertices
+            + self.patch
+            + self.image
+            + self.image_rect
+            + self.image_lattice
+            + self.atlas
+            + self.edge_aa_image_set
+            + self.text_blob
+            + self.glyphs
+            + self.annotation
+            + self.shadow
+            + self.drawable
+            + self.picture
+            + self.edge_aa_quad
+    }
+}
+
+/// A [`NWayCanvas`] that counts the draw calls it receives, for example to test that a piece of
+/// code draws exactly the primitives expected, without comparing pixels.
+///
+/// Draw calls are counted even if no canvas is added to forward them to.
+///
+/// Access to the [`NWayCanvas`] and [`crate::Canvas`] functions are resolved with the [`Deref`]
+/// trait.
+pub struct CountingCanvas<'lt> {
+    nway: NWayCanvas<'lt>,
+}
+
+impl<'lt> Deref for CountingCanvas<'lt> {
+    type Target = NWayCanvas<'lt>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.nway
+    }
+}
+
+impl DerefMut for CountingCanvas<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.nway
+    }
+}
+
+impl fmt::Debug for CountingCanvas<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountingCanvas")
+            .field("nway", &self.nway)
+            .field("counts", &self.counts())
+            .finish()
+    }
+}
+
+impl CountingCanvas<'_> {
+    /// Creates a [`CountingCanvas`] of the given dimensions with all counts set to zero.
+    pub fn new(size: impl Into<ISize>) -> Self {
+        let size = size.into();
+        Self {
+            nway: NWayCanvas::from_native_ptr(unsafe {
+                sb::C_CountingCanvas_new(size.width, size.height)
+            }),
+        }
+    }
+
+    /// The draw calls counted since creation or the last call to [`Self::reset_counts()`].
+    pub fn counts(&self) -> DrawCounts {
+        let native = self.nway.native();
+        let count = |kind: DrawKind| unsafe { sb::C_CountingCanvas_count(native, kind) };
+        DrawCounts {
+            paint: count(DrawKind::Paint),
+            behind: count(DrawKind::Behind),
+            points: count(DrawKind::Points),
+            rect: count(DrawKind::Rect),
+            region: count(DrawKind::Region),
+            oval: count(DrawKind::Oval),
+            arc: count(DrawKind::Arc),
+            rrect: count(DrawKind::RRect),
+            drrect: count(DrawKind::DRRect),
+            path: count(DrawKind::Path),
+            vertices: count(DrawKind::Vertices),
+            patch: count(DrawKind::Patch),
+            image: count(DrawKind::Image),
+            image_rect: count(DrawKind::ImageRect),
+            image_lattice: count(DrawKind::ImageLattice),
+            atlas: count(DrawKind::Atlas),
+            edge_aa_image_set: count(DrawKind::EdgeAAImageSet),
+            text_blob: count(DrawKind::TextBlob),
+            glyphs: count(DrawKind::Glyphs),
+            annotation: count(DrawKind::Annotation),
+            shadow: count(DrawKind::Shadow),
+            drawable: count(DrawKind::Drawable),
+            picture: count(DrawKind::Picture),
+            edge_aa_quad: count(DrawKind::EdgeAAQuad),
+        }
+    }
+
+    /// Sets all counts to zero.
+    pub fn reset_counts(&mut self) -> &mut Self {
+        unsafe { sb::C_CountingCanvas_resetCounts(self.nway.native_mut()) }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CountingCanvas, DrawCounts, DrawKind};
+    use crate::{
+        AlphaType, Canvas, Color, ColorType, Font, ImageInfo, Paint, RRect, Rect, TextBlob,
+        Typeface,
+    };
+
+    #[test]
+    fn counts_draw_calls() {
+        let mut canvas = CountingCanvas::new((100, 100));
+        let paint = Paint::default();
+        let font = Font::new(Typeface::default(), 24.0);
+        let blob = TextBlob::from_str("Hi", &font).unwrap();
+
+        canvas.draw_rrect(
+            RRect::new_rect_xy(Rect::from_wh(50.0, 20.0), 4.0, 4.0),
+            &paint,
+        );
+        canvas.draw_text_blob(&blob, (10, 10), &paint);
+        canvas.draw_text_blob(&blob, (10, 40), &paint);
+
+        let counts = canvas.counts();
+        assert_eq!(1, counts.rrect);
+        assert_eq!(2, counts.text_blob);
+        assert_eq!(3, counts.total());
+
+        canvas.reset_counts();
+        assert_eq!(DrawCounts::default(), canvas.counts());
+    }
+
+    #[test]
+    fn forwards_counted_draw_calls() {
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        {
+            let mut target = Canvas::from_raster_direct(&info, &mut pixels, None, None).unwrap();
+            let mut canvas = CountingCanvas::new((4, 4));
+            canvas.add_canvas(&mut target);
+            canvas.clear(Color::RED);
+            assert_eq!(1, canvas.counts().paint);
+        }
+        let red = [0xff, 0, 0, 0xff];
+        assert!(pixels.chunks(4).all(|p| p == red));
+    }
+
+    #[test]
+    fn every_draw_kind_is_counted() {
+        // `DrawCounts` needs a field for every `DrawKind`.
+        assert_eq!(24, DrawKind::DrawKindCount as usize);
+    }
+}
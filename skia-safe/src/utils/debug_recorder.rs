@@ -0,0 +1,73 @@
+use crate::{Canvas, Data, PictureRecorder, Rect};
+use std::fmt;
+
+/// Records the draw calls issued to its canvas into a [`crate::Picture`] and serializes it, so
+/// that the exact sequence of draw commands can be stored and compared across runs, for example
+/// in golden tests.
+///
+/// The serialized data can be turned back into a picture with [`crate::Picture::from_data()`]
+/// and replayed with [`crate::Picture::playback()`] or [`Canvas::draw_picture()`].
+pub struct DebugRecorder {
+    recorder: PictureRecorder,
+}
+
+impl fmt::Debug for DebugRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugRecorder").finish()
+    }
+}
+
+impl DebugRecorder {
+    /// Starts recording the draw calls that are issued inside of `bounds`.
+    pub fn new(bounds: impl AsRef<Rect>) -> Self {
+        let mut recorder = PictureRecorder::new();
+        recorder.begin_recording(bounds, None);
+        Self { recorder }
+    }
+
+    /// The canvas to issue the draw calls to.
+    pub fn canvas(&mut self) -> &mut Canvas {
+        self.recorder.recording_canvas().unwrap()
+    }
+
+    /// Finishes recording and returns the serialized picture.
+    pub fn finish(mut self) -> Option<Data> {
+        self.recorder
+            .finish_recording_as_picture(None)
+            .map(|picture| picture.serialize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DebugRecorder;
+    use crate::{Canvas, Color, Paint, Picture, Rect, Surface};
+
+    fn draw(canvas: &mut Canvas) {
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+        paint.set_color(Color::BLUE);
+        canvas.clear(Color::WHITE);
+        canvas.draw_circle((8, 8), 5.0, &paint);
+        paint.set_color(Color::RED);
+        canvas.draw_rect(Rect::from_xywh(2.0, 10.0, 12.0, 3.0), &paint);
+    }
+
+    #[test]
+    fn replays_serialized_draw_calls() {
+        let mut recorder = DebugRecorder::new(Rect::from_wh(16.0, 16.0));
+        draw(recorder.canvas());
+        let data = recorder.finish().unwrap();
+
+        let picture = Picture::from_data(&data).unwrap();
+        let mut replayed = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        picture.playback(replayed.canvas());
+
+        let mut expected = Surface::new_raster_n32_premul((16, 16)).unwrap();
+        draw(expected.canvas());
+
+        let replayed = replayed.peek_pixels().unwrap();
+        let expected = expected.peek_pixels().unwrap();
+        assert_eq!(replayed.bytes().unwrap(), expected.bytes().unwrap());
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{prelude::*, Canvas, ISize, OwnedCanvas};
+use skia_bindings::{self as sb, SkCanvas};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// A [`Canvas`] that forwards all draw calls, and changes to the matrix and clip, to a number of
+/// other canvases.
+///
+/// The canvases that are added are borrowed for the lifetime of the [`NWayCanvas`].
+///
+/// Access to the [`Canvas`] functions are resolved with the [`Deref`] trait.
+pub struct NWayCanvas<'lt> {
+    canvas: OwnedCanvas<'static>,
+    canvases: Vec<ptr::NonNull<SkCanvas>>,
+    pd: PhantomData<&'lt mut Canvas>,
+}
+
+impl Deref for NWayCanvas<'_> {
+    type Target = Canvas;
+
+    fn deref(&self) -> &Self::Target {
+        &self.canvas
+    }
+}
+
+impl DerefMut for NWayCanvas<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.canvas
+    }
+}
+
+impl fmt::Debug for NWayCanvas<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NWayCanvas")
+            .field("canvas", &self.canvas)
+            .field("canvas_count", &self.canvas_count())
+            .finish()
+    }
+}
+
+impl<'lt> NWayCanvas<'lt> {
+    /// Creates a [`NWayCanvas`] of the given dimensions that does not forward to any canvas yet.
+    pub fn new(size: impl Into<ISize>) -> Self {
+        let size = size.into();
+        Self::from_native_ptr(unsafe { sb::C_SkNWayCanvas_new(size.width, size.height) })
+    }
+
+    /// Takes ownership of a `SkNWayCanvas`, or of a canvas derived from it.
+    pub(crate) fn from_native_ptr(canvas: *mut SkCanvas) -> Self {
+        Self {
+            canvas: Canvas::own_from_native_ptr(canvas).unwrap(),
+            canvases: Vec::new(),
+            pd: PhantomData,
+        }
+    }
+
+    /// Adds `canvas` to the canvases all subsequent calls are forwarded to.
+    pub fn add_canvas(&mut self, canvas: &'lt mut Canvas) -> &mut Self {
+        let canvas = ptr::NonNull::from(canvas.native_mut());
+        unsafe { sb::C_SkNWayCanvas_addCanvas(self.canvas.native_mut(), canvas.as_ptr()) }
+        self.canvases.push(canvas);
+        self
+    }
+
+    /// Stops forwarding to the canvas that was added at `index`, see [`Self::add_canvas()`].
+    ///
+    /// The canvas stays borrowed for the lifetime of the [`NWayCanvas`].
+    pub fn remove_canvas(&mut self, index: usize) -> &mut Self {
+        let canvas = self.canvases.remove(index);
+        unsafe { sb::C_SkNWayCanvas_removeCanvas(self.canvas.native_mut(), canvas.as_ptr()) }
+        self
+    }
+
+    /// Stops forwarding to all canvases.
+    pub fn remove_all(&mut self) -> &mut Self {
+        unsafe { sb::C_SkNWayCanvas_removeAll(self.canvas.native_mut()) }
+        self.canvases.clear();
+        self
+    }
+
+    /// The number of canvases the calls are forwarded to.
+    pub fn canvas_count(&self) -> usize {
+        self.canvases.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NWayCanvas;
+    use crate::{AlphaType, Canvas, Color, ColorType, ImageInfo};
+
+    #[test]
+    fn draws_into_all_canvases() {
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixels1 = vec![0u8; info.compute_min_byte_size()];
+        let mut pixels2 = vec![0u8; info.compute_min_byte_size()];
+        {
+            let mut canvas1 = Canvas::from_raster_direct(&info, &mut pixels1, None, None).unwrap();
+            let mut canvas2 = Canvas::from_raster_direct(&info, &mut pixels2, None, None).unwrap();
+            let mut nway = NWayCanvas::new((4, 4));
+            nway.add_canvas(&mut canvas1).add_canvas(&mut canvas2);
+            assert_eq!(2, nway.canvas_count());
+            nway.clear(Color::RED);
+        }
+        let red = [0xff, 0, 0, 0xff];
+        assert!(pixels1.chunks(4).all(|p| p == red));
+        assert!(pixels2.chunks(4).all(|p| p == red));
+    }
+
+    #[test]
+    fn removed_canvases_are_not_drawn_to() {
+        let info = ImageInfo::new((4, 4), ColorType::RGBA8888, AlphaType::Unpremul, None);
+        let mut pixels = vec![0u8; info.compute_min_byte_size()];
+        {
+            let mut canvas = Canvas::from_raster_direct(&info, &mut pixels, None, None).unwrap();
+            let mut nway = NWayCanvas::new((4, 4));
+            nway.add_canvas(&mut canvas).remove_canvas(0);
+            assert_eq!(0, nway.canvas_count());
+            nway.clear(Color::RED);
+        }
+        assert!(pixels.iter().all(|&b| b == 0));
+    }
+}
@@ -3,9 +3,16 @@ use skia_bindings::{self as sb, SkShadowUtils};
 
 bitflags! {
     pub struct ShadowFlags: u32 {
+        /// The occluding object is not opaque. Knowing that the occluder is opaque allows
+        /// us to cull shadow geometry behind it and improve performance.
         const TRANSPARENT_OCCLUDER = sb::SkShadowFlags_kTransparentOccluder_ShadowFlag as u32;
+        /// Don't try to use analytic shadows.
         const GEOMETRIC_ONLY = sb::SkShadowFlags_kGeometricOnly_ShadowFlag as u32;
-        const ALL = Self::TRANSPARENT_OCCLUDER.bits | Self::GEOMETRIC_ONLY.bits;
+        /// Light position represents a direction, light radius is blur radius at elevation 1.
+        const DIRECTIONAL_LIGHT = sb::SkShadowFlags_kDirectionalLight_ShadowFlag as u32;
+        const ALL = Self::TRANSPARENT_OCCLUDER.bits
+            | Self::GEOMETRIC_ONLY.bits
+            | Self::DIRECTIONAL_LIGHT.bits;
     }
 }
 
@@ -99,3 +106,29 @@ pub fn compute_tonal_colors(
     }
     (out_ambient_color, out_spot_color)
 }
+
+#[test]
+fn draw_shadow_of_rounded_rect() {
+    use crate::{RRect, Surface};
+
+    let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+    let canvas = surface.canvas();
+    canvas.clear(Color::TRANSPARENT);
+    let path = Path::rrect(
+        RRect::new_rect_xy(Rect::new(30.0, 30.0, 70.0, 70.0), 5.0, 5.0),
+        None,
+    );
+    canvas.draw_shadow(
+        &path,
+        Point3::new(0.0, 0.0, 8.0),
+        Point3::new(50.0, 0.0, 600.0),
+        80.0,
+        Color::from_argb(0x40, 0, 0, 0),
+        Color::from_argb(0x80, 0, 0, 0),
+        None,
+    );
+    let pixmap = canvas.peek_pixels().unwrap();
+    assert_eq!(pixmap.get_color((5, 5)).a(), 0);
+    // the shadow extends outside of the path.
+    assert_ne!(pixmap.get_color((50, 72)).a(), 0);
+}
@@ -31,6 +31,10 @@ pub fn draw_str(
 }
 
 impl Canvas {
+    /// Draws `text` like [`Canvas::draw_str()`], but aligned horizontally relative to `p`.
+    ///
+    /// With [`Align::Center`] or [`Align::Right`], the text is shifted left by half or all of its
+    /// advance width as measured by [`Font::measure_str()`]. The baseline stays at `p.y`.
     pub fn draw_str_align(
         &mut self,
         text: impl AsRef<str>,
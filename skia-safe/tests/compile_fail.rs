@@ -0,0 +1,9 @@
+//! Tests that code misusing the API is rejected by the compiler.
+//!
+//! Doc tests are disabled for this crate, so `compile_fail` doc tests would never run.
+
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,8 @@
+use skia_safe::{AutoCanvasRestore, OwnedCanvas};
+
+fn main() {
+    let mut canvas = OwnedCanvas::default();
+    let guard = AutoCanvasRestore::guard(&mut canvas, true);
+    canvas.save();
+    drop(guard);
+}